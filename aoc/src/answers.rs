@@ -0,0 +1,110 @@
+//! Accepted answers for the real puzzle inputs, stored in `answers.toml`.
+//!
+//! Each day has a table with an optional answer for each part:
+//!
+//! ```toml
+//! [day1]
+//! part1 = 1234
+//! part2 = "5678"
+//! ```
+//!
+//! If `answers.toml` is missing, its [encrypted](input::crypt) version `answers.toml.enc` is
+//! decrypted instead.
+
+use input::{crypt, CryptError, Key};
+use serde::Deserialize;
+use solution::samples::Answer;
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Default location of the answers file, in the workspace root.
+pub(crate) fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml")
+}
+
+/// Error returned when the answers file cannot be loaded.
+#[derive(Debug, Error)]
+pub(crate) enum AnswersError {
+    #[error("failed to read `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: CryptError },
+    #[error("failed to parse `{}`: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+/// Answers of a single day.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// Accepted answers of every day, keyed by `dayN`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct Answers(BTreeMap<String, DayAnswers>);
+
+impl Answers {
+    /// Load answers from the file at `path`, or decrypt them from its encrypted version.
+    pub(crate) fn load(path: &Path) -> Result<Self, AnswersError> {
+        let io_error = |source| AnswersError::Io {
+            path: path.to_owned(),
+            source,
+        };
+        let s = crypt::read_or_decrypt(path, Key::from_env).map_err(io_error)?;
+        let s = String::from_utf8(s)
+            .map_err(|e| io_error(io::Error::new(io::ErrorKind::InvalidData, e).into()))?;
+        Self::parse(&s).map_err(|source| AnswersError::Parse {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    /// Parse answers from the contents of an answers file.
+    pub(crate) fn parse(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    /// Accepted answer to `part` of `day`, if stored.
+    pub(crate) fn get(&self, day: u8, part: u8) -> Option<String> {
+        let answers = self.0.get(&format!("day{day}"))?;
+        let answer = match part {
+            1 => answers.part1.as_ref(),
+            2 => answers.part2.as_ref(),
+            _ => None,
+        }?;
+        Some(answer.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+
+    /// Verify that answers can be stored as integers or strings and may be omitted.
+    #[test]
+    fn parse() {
+        let answers = Answers::parse(
+            r#"
+            [day1]
+            part1 = 11
+            part2 = " 31\n"
+
+            [day4]
+            part1 = 18
+            "#,
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1).as_deref(), Some("11"));
+        assert_eq!(answers.get(1, 2).as_deref(), Some("31"));
+        assert_eq!(answers.get(4, 2), None);
+        assert_eq!(answers.get(2, 1), None);
+        assert!(Answers::parse("[day1]\npart3 = 1").is_err());
+    }
+}
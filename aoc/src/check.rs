@@ -0,0 +1,115 @@
+//! Regression check of every day against the real puzzle inputs.
+
+use crate::{answers::Answers, days::Day};
+
+/// Result of checking a single part.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum Status {
+    /// The answer matches the stored answer.
+    Ok(String),
+    /// The answer does not match the stored answer.
+    Mismatch { expected: String, actual: String },
+    /// No answer is stored for the part.
+    Unchecked(String),
+    /// Solving the part failed.
+    Failed(String),
+}
+
+impl Status {
+    /// Compare the result of solving a part with the stored answer.
+    pub(crate) fn new(expected: Option<String>, actual: Result<String, String>) -> Self {
+        match (expected, actual) {
+            (_, Err(e)) => Status::Failed(e),
+            (None, Ok(actual)) => Status::Unchecked(actual),
+            (Some(expected), Ok(actual)) if expected == actual => Status::Ok(actual),
+            (Some(expected), Ok(actual)) => Status::Mismatch { expected, actual },
+        }
+    }
+
+    /// Whether the check of this part failed.
+    /// Failures of parts without a stored answer are not counted.
+    pub(crate) fn is_failure(&self, has_answer: bool) -> bool {
+        match self {
+            Status::Ok(_) | Status::Unchecked(_) => false,
+            Status::Mismatch { .. } => true,
+            Status::Failed(_) => has_answer,
+        }
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Ok(answer) => write!(f, "ok ({answer})"),
+            Status::Mismatch { expected, actual } => {
+                write!(f, "MISMATCH (expected {expected}, got {actual})")
+            }
+            Status::Unchecked(answer) => write!(f, "no stored answer ({answer})"),
+            Status::Failed(e) => write!(f, "FAILED ({e})"),
+        }
+    }
+}
+
+/// Solve every day in `days` against its real input and print the status of each part.
+/// Inputs are loaded with `load_input`.
+/// Returns the number of failed checks.
+pub(crate) fn run(
+    days: &[Day],
+    answers: &Answers,
+    load_input: impl Fn(u8) -> Result<Vec<u8>, String>,
+) -> usize {
+    let mut failures = 0;
+    for day in days {
+        let results = match load_input(day.day) {
+            Ok(input) => day.solve(&input),
+            Err(e) => [1, 2].map(|_| Err(format!("input unavailable: {e}"))),
+        };
+        for (part, result) in (1..=2).zip(results) {
+            let expected = answers.get(day.day, part);
+            let has_answer = expected.is_some();
+            let status = Status::new(expected, result);
+            if status.is_failure(has_answer) {
+                failures += 1;
+            }
+            println!("day {} part {part}: {status}", day.day);
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, Status};
+    use crate::{answers::Answers, days};
+
+    /// Verify that results are compared with stored answers.
+    #[test]
+    fn status() {
+        let ok = |s: &str| Ok(s.to_owned());
+        assert_eq!(
+            Status::new(Some("1".into()), ok("1")),
+            Status::Ok("1".into())
+        );
+        assert!(Status::new(Some("1".into()), ok("2")).is_failure(true));
+        assert!(!Status::new(None, ok("2")).is_failure(false));
+        assert!(Status::new(Some("1".into()), Err("panic".into())).is_failure(true));
+        assert!(!Status::new(None, Err("panic".into())).is_failure(false));
+    }
+
+    /// Verify the runner on sample inputs, with stored answers for some of the parts.
+    #[test]
+    fn sample_inputs() {
+        let answers =
+            Answers::parse("[day1]\npart1 = 11\npart2 = 31\n[day2]\npart1 = 2\npart2 = 5\n")
+                .unwrap();
+        let load_input = |day| match day {
+            1 => Ok(include_bytes!("../../day1/sample_input.txt").to_vec()),
+            2 => Ok(include_bytes!("../../day2/sample_input.txt").to_vec()),
+            _ => Err("missing".to_owned()),
+        };
+        // Day 2 part 2 is 4, not 5.
+        assert_eq!(run(&days::DAYS[..2], &answers, load_input), 1);
+        // Missing inputs only fail days with stored answers.
+        assert_eq!(run(&days::DAYS[2..], &answers, load_input), 0);
+    }
+}
@@ -0,0 +1,63 @@
+//! Registry of the solutions of every day.
+
+use bench::catch_panic;
+use solution::Solution;
+
+/// Answer to a part, or the panic message if solving it failed.
+pub(crate) type PartResult = Result<String, String>;
+
+/// Solution of a single day with its [`Solution`] implementation erased.
+#[derive(Clone, Copy)]
+pub(crate) struct Day {
+    pub(crate) day: u8,
+    solve: fn(&[u8]) -> [PartResult; 2],
+    bench: fn(&bench::Config, &[u8]) -> bench::Report,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Day {
+            day: S::DAY,
+            solve: solve::<S>,
+            bench: bench::bench_solution::<S>,
+        }
+    }
+
+    /// Solve both parts on `input`. Panics are caught and returned as errors.
+    pub(crate) fn solve(&self, input: &[u8]) -> [PartResult; 2] {
+        (self.solve)(input)
+    }
+
+    /// Benchmark parsing and both parts on `input`.
+    pub(crate) fn bench(&self, config: &bench::Config, input: &[u8]) -> bench::Report {
+        (self.bench)(config, input)
+    }
+}
+
+/// Solutions of every day, in order.
+pub(crate) const DAYS: [Day; 4] = [
+    Day::of::<day1::Day1>(),
+    Day::of::<day2::Day2>(),
+    Day::of::<day3::Day3>(),
+    Day::of::<day4::Day4>(),
+];
+
+/// Find the solution of `day`.
+pub(crate) fn find(day: u8) -> Option<Day> {
+    DAYS.into_iter().find(|d| d.day == day)
+}
+
+/// Solve both parts of `S`, catching panics.
+fn solve<S: Solution>(input: &[u8]) -> [PartResult; 2] {
+    let parsed = match catch_panic(|| S::parse(input)) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let e = format!("parsing failed: {e}");
+            return [Err(e.clone()), Err(e)];
+        }
+    };
+    [
+        catch_panic(|| S::part1(&parsed)),
+        catch_panic(|| S::part2(&parsed)),
+    ]
+}
@@ -0,0 +1,304 @@
+//! Runner for the solutions of every day.
+
+use clap::{Parser, Subcommand};
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+mod answers;
+mod check;
+mod days;
+mod scaffold;
+
+/// Runner for the solutions of every day.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve days against their real inputs and compare with stored answers.
+    Check {
+        /// Days to check. Checks every day if empty.
+        days: Vec<u8>,
+        /// Path to the answers file. Defaults to `answers.toml` in the workspace root.
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Benchmark days against their real inputs.
+    Bench {
+        /// Days to benchmark. Benchmarks every day if empty.
+        days: Vec<u8>,
+        /// Print results as JSON.
+        #[arg(long)]
+        json: bool,
+        /// Number of samples taken of each function.
+        #[arg(long, default_value_t = bench::Config::default().samples)]
+        samples: usize,
+        /// Warmup time of each function, in milliseconds.
+        #[arg(long, default_value_t = bench::Config::default().warmup.as_millis() as u64)]
+        warmup: u64,
+        /// Save results to the baseline with this name.
+        #[arg(long, value_name = "NAME")]
+        save_baseline: Option<String>,
+        /// Compare results with the baseline with this name.
+        #[arg(long, value_name = "NAME")]
+        baseline: Option<String>,
+        /// Percentage a function may slow down by before it's flagged as a regression.
+        #[arg(long, default_value_t = bench::baseline::DEFAULT_THRESHOLD)]
+        threshold: f64,
+    },
+    /// Generate an input and print it to standard output.
+    Gen {
+        day: u8,
+        /// Size of the input, in the unit used by the day's generator
+        /// (lines, reports, instructions or cells). Defaults to the size used by benchmarks.
+        #[arg(long)]
+        size: Option<usize>,
+        /// Seed of the generator. Defaults to `AOC_SEED`, or a random seed if not set.
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Encrypt real inputs and answers so they can be committed to the repository.
+    ///
+    /// Files that are missing are skipped, as are files whose encrypted version is up to date.
+    Encrypt {
+        /// Days to encrypt the inputs of. Encrypts the inputs of every day if empty.
+        days: Vec<u8>,
+        /// Path to the answers file. Defaults to `answers.toml` in the workspace root.
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Create the crate of a new day and add it to the workspace.
+    ///
+    /// The crate has a solution with unfinished parts, an empty sample input,
+    /// a sample manifest, a binary and a benchmark.
+    New { day: u8 },
+    /// Generate a new key for encrypting inputs and write it to the key file.
+    Keygen {
+        /// Overwrite the key file if it already exists.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Check { days, answers } => check(days, answers),
+        Command::Bench {
+            days,
+            json,
+            samples,
+            warmup,
+            save_baseline,
+            baseline,
+            threshold,
+        } => {
+            let config = bench::Config {
+                samples,
+                warmup: Duration::from_millis(warmup),
+                ..bench::Config::default()
+            };
+            let options = bench::baseline::Options {
+                save: save_baseline,
+                compare: baseline,
+                threshold,
+            };
+            bench(days, json, config, options)
+        }
+        Command::Gen { day, size, seed } => gen(day, size, seed),
+        Command::Encrypt { days, answers } => encrypt(days, answers),
+        Command::New { day } => new(day),
+        Command::Keygen { force } => keygen(force),
+    }
+}
+
+/// Run [`Command::Check`].
+fn check(days: Vec<u8>, answers: Option<PathBuf>) -> ExitCode {
+    let answers = match answers::Answers::load(&answers.unwrap_or_else(answers::default_path)) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let days = match select_days(&days) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = input::Client::from_env();
+    let load_input = |day| {
+        client
+            .fetch_input(day)
+            .map(String::into_bytes)
+            .map_err(|e| e.to_string())
+    };
+    match check::run(&days, &answers, load_input) {
+        0 => ExitCode::SUCCESS,
+        failures => {
+            eprintln!("{failures} check(s) failed");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run [`Command::Bench`].
+fn bench(
+    days: Vec<u8>,
+    json: bool,
+    config: bench::Config,
+    options: bench::baseline::Options,
+) -> ExitCode {
+    let days = match select_days(&days) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = input::Client::from_env();
+    let mut reports = vec![];
+    for day in days {
+        let input = match client.fetch_input(day.day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("failed to load input of day {}: {e}", day.day);
+                return ExitCode::FAILURE;
+            }
+        };
+        let report = day.bench(&config, input.as_bytes());
+        if !json {
+            println!("{report}");
+        }
+        reports.push(report);
+    }
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&reports).expect("reports serialize to JSON")
+        );
+    }
+    match options.apply("real", &reports) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(regressions) => {
+            eprintln!("{regressions} regression(s) beyond {}%", options.threshold);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run [`Command::Gen`].
+fn gen(day: u8, size: Option<usize>, seed: Option<u64>) -> ExitCode {
+    let Some(size) = size.or_else(|| generate::default_size(day)) else {
+        eprintln!("no generator for day {day}");
+        return ExitCode::FAILURE;
+    };
+    let mut rng = seed.map_or_else(generate::Seeded::from_env, generate::Seeded::new);
+    let input = generate::input(day, &mut rng, size).expect("day has a generator");
+    eprintln!("generated with seed {}", rng.seed());
+    print!("{input}");
+    ExitCode::SUCCESS
+}
+
+/// Run [`Command::Encrypt`].
+fn encrypt(days: Vec<u8>, answers: Option<PathBuf>) -> ExitCode {
+    let client = input::Client::from_env();
+    let key = match client.key() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let days = match select_days(&days) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let paths = days.iter().map(|day| client.input_path(day.day));
+    let paths = paths.chain([answers.unwrap_or_else(answers::default_path)]);
+    let mut failures = 0;
+    for path in paths {
+        if !path.exists() {
+            continue;
+        }
+        match input::crypt::encrypt_file(&key, &path) {
+            Ok(true) => println!("encrypted {}", path.display()),
+            Ok(false) => println!("unchanged {}", path.display()),
+            Err(e) => {
+                eprintln!("failed to encrypt {}: {e}", path.display());
+                failures += 1;
+            }
+        }
+    }
+    match failures {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+/// Run [`Command::New`].
+fn new(day: u8) -> ExitCode {
+    if !(1..=25).contains(&day) {
+        eprintln!("day must be between 1 and 25, got {day}");
+        return ExitCode::FAILURE;
+    }
+    let root = scaffold::workspace_root();
+    match scaffold::create(&root, day) {
+        Ok(created) => {
+            for path in created {
+                println!("wrote {}", path.display());
+            }
+            println!(
+                "add the sample input and its answers to `day{day}/samples.toml`, \
+                 then `day{day}::Day{day}` to `aoc/src/days.rs` to run it with `aoc`"
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run [`Command::Keygen`].
+fn keygen(force: bool) -> ExitCode {
+    let path = std::env::var_os(input::crypt::KEYFILE_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(input::crypt::default_keyfile);
+    if path.exists() && !force {
+        eprintln!(
+            "key file {} already exists (use `--force` to overwrite it)",
+            path.display()
+        );
+        return ExitCode::FAILURE;
+    }
+    if let Err(e) = std::fs::write(&path, format!("{}\n", input::Key::generate())) {
+        eprintln!("failed to write key file {}: {e}", path.display());
+        return ExitCode::FAILURE;
+    }
+    println!("wrote key to {}", path.display());
+    ExitCode::SUCCESS
+}
+
+/// Find the solutions of the selected days. Selects every day if `days` is empty.
+fn select_days(days: &[u8]) -> Result<Vec<days::Day>, String> {
+    match days {
+        [] => Ok(days::DAYS.to_vec()),
+        days => days
+            .iter()
+            .map(|&day| days::find(day).ok_or_else(|| format!("no solution for day {day}")))
+            .collect(),
+    }
+}
@@ -0,0 +1,191 @@
+//! Scaffolding of the crate of a new day.
+//!
+//! The crate is generated from the templates in `aoc/templates`, with `{{day}}` replaced by
+//! the day, and registered in the members and dependencies of the workspace manifest.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Files of a new day's crate, relative to the crate, and their templates.
+const TEMPLATES: [(&str, &str); 6] = [
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.in")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.in")),
+    ("src/main.rs", include_str!("../templates/main.rs.in")),
+    (
+        "benches/day{{day}}.rs",
+        include_str!("../templates/bench.rs.in"),
+    ),
+    ("samples.toml", include_str!("../templates/samples.toml.in")),
+    ("sample_input.txt", ""),
+];
+
+/// Root of the workspace this runner belongs to.
+pub(crate) fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// Error returned when a day cannot be scaffolded.
+#[derive(Debug, Error)]
+pub(crate) enum ScaffoldError {
+    #[error("`{}` already exists", path.display())]
+    Exists { path: PathBuf },
+    #[error("failed to access `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("failed to parse `{}`: {reason}", path.display())]
+    Manifest { path: PathBuf, reason: String },
+}
+
+/// Create the crate of `day` in the workspace at `root` and register it in the workspace
+/// manifest. Returns the paths of the created files.
+///
+/// The manifest is checked before anything is written, so a failure leaves the workspace
+/// unchanged unless writing a file fails.
+pub(crate) fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let name = format!("day{day}");
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(ScaffoldError::Exists { path: dir });
+    }
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = read(&manifest_path)?;
+    let manifest = register(&manifest, &name).map_err(|reason| ScaffoldError::Manifest {
+        path: manifest_path.clone(),
+        reason,
+    })?;
+    let mut created = vec![];
+    for (file, template) in TEMPLATES {
+        let path = dir.join(fill(file, day));
+        let io_error = |source| ScaffoldError::Io {
+            path: path.clone(),
+            source,
+        };
+        fs::create_dir_all(path.parent().expect("files are in the crate")).map_err(io_error)?;
+        fs::write(&path, fill(template, day)).map_err(io_error)?;
+        created.push(path);
+    }
+    fs::write(&manifest_path, manifest).map_err(|source| ScaffoldError::Io {
+        path: manifest_path.clone(),
+        source,
+    })?;
+    created.push(manifest_path);
+    Ok(created)
+}
+
+/// Replace the placeholders in `template` with `day`.
+fn fill(template: &str, day: u8) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+/// Read the file at `path` to a string.
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|source| ScaffoldError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Add the crate `name` to the members and dependencies of the workspace `manifest`.
+///
+/// Members are kept in natural order, so `day10` follows `day9`. The dependency is added
+/// after the last dependency on another day, or at the end of the dependencies table.
+fn register(manifest: &str, name: &str) -> Result<String, String> {
+    let mut lines: Vec<String> = manifest.lines().map(str::to_owned).collect();
+    let members = lines
+        .iter()
+        .position(|line| line.starts_with("members = ["))
+        .ok_or("no single-line `members` list in `[workspace]`")?;
+    #[derive(serde::Deserialize)]
+    struct Members {
+        members: Vec<String>,
+    }
+    let mut list = toml::from_str::<Members>(&lines[members])
+        .map_err(|e| e.to_string())?
+        .members;
+    if list.iter().any(|member| member == name) {
+        return Err(format!("`{name}` is already a member"));
+    }
+    list.push(name.to_owned());
+    list.sort_by(|a, b| natural_key(a).cmp(&natural_key(b)));
+    let quoted: Vec<_> = list.iter().map(|member| format!("{member:?}")).collect();
+    lines[members] = format!("members = [{}]", quoted.join(", "));
+
+    let table = lines
+        .iter()
+        .position(|line| line.trim() == "[workspace.dependencies]")
+        .ok_or("no `[workspace.dependencies]` table")?;
+    let table_end = lines[table + 1..]
+        .iter()
+        .position(|line| line.starts_with('['))
+        .map_or(lines.len(), |i| table + 1 + i);
+    let entries = &lines[table + 1..table_end];
+    let is_day = |line: &String| line.split_once(" = ").is_some_and(|(key, _)| is_day(key));
+    let after = entries
+        .iter()
+        .rposition(is_day)
+        .or_else(|| entries.iter().rposition(|line| !line.trim().is_empty()))
+        .map_or(table, |i| table + 1 + i);
+    lines.insert(after + 1, format!("{name} = {{ path = {name:?} }}"));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Whether `name` is the name of a day's crate.
+fn is_day(name: &str) -> bool {
+    name.strip_prefix("day")
+        .is_some_and(|day| day.parse::<u8>().is_ok())
+}
+
+/// Key that orders names by their text, then by their trailing number.
+fn natural_key(name: &str) -> (&str, u64) {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    (prefix, name[prefix.len()..].parse().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create, register, ScaffoldError};
+
+    const MANIFEST: &str = r#"[workspace]
+resolver = "2"
+members = ["aoc", "day1", "day9", "generate"]
+
+[workspace.dependencies]
+input = { path = "input" }
+day1 = { path = "day1" }
+day9 = { path = "day9" }
+rayon = "1.10.0"
+"#;
+
+    /// Verify that a day is added to the members and dependencies in order.
+    #[test]
+    fn register_day() {
+        let manifest = register(MANIFEST, "day10").unwrap();
+        assert!(manifest.contains(r#"members = ["aoc", "day1", "day9", "day10", "generate"]"#));
+        assert!(manifest.contains(
+            "day9 = { path = \"day9\" }\nday10 = { path = \"day10\" }\nrayon = \"1.10.0\"\n"
+        ));
+        assert!(register(&manifest, "day10").is_err());
+    }
+
+    /// Verify that every file is created from the templates and existing days are refused.
+    #[test]
+    fn create_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+        let created = create(&root, 12).unwrap();
+        assert_eq!(created.len(), 7);
+        let lib = std::fs::read_to_string(root.join("day12/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day12;") && !lib.contains("{{"));
+        assert!(root.join("day12/benches/day12.rs").exists());
+        let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("day12 = { path = \"day12\" }"));
+        assert!(matches!(
+            create(&root, 12),
+            Err(ScaffoldError::Exists { .. })
+        ));
+    }
+}
@@ -0,0 +1,318 @@
+//! Storage of benchmark results as named baselines, and comparison against them.
+//!
+//! A baseline is a JSON file in the [baselines directory](baselines_dir) holding the
+//! [`Report`] of each day, grouped by the kind of input the day was benchmarked on
+//! (e.g. generated or real inputs), since those results are not comparable.
+
+use crate::Report;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use thiserror::Error;
+
+/// Environment variable that overrides the [baselines directory](baselines_dir).
+pub const BASELINES_DIR_ENV: &str = "AOC_BASELINES_DIR";
+/// Default percentage a function may slow down by before it's flagged as a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Directory baselines are stored in.
+///
+/// Defaults to `baselines` in the workspace root.
+/// Can be overridden with the [`BASELINES_DIR_ENV`] environment variable.
+pub fn baselines_dir() -> PathBuf {
+    env::var_os(BASELINES_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../baselines"))
+}
+
+/// Error returned when a baseline cannot be loaded or saved.
+#[derive(Debug, Error)]
+pub enum BaselineError {
+    #[error("failed to access baseline `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("failed to parse baseline `{}`: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+/// Benchmark results of each day, grouped by the kind of input.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline(BTreeMap<String, BTreeMap<u8, Report>>);
+
+impl Baseline {
+    /// Path of the baseline called `name`.
+    pub fn path(name: &str) -> PathBuf {
+        baselines_dir().join(format!("{name}.json"))
+    }
+
+    /// Load the baseline at `path`.
+    pub fn load(path: &Path) -> Result<Self, BaselineError> {
+        let json = fs::read_to_string(path).map_err(|source| BaselineError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        serde_json::from_str(&json).map_err(|source| BaselineError::Json {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    /// Load the baseline at `path`, or an empty one if it doesn't exist yet.
+    pub fn load_or_default(path: &Path) -> Result<Self, BaselineError> {
+        match Self::load(path) {
+            Err(BaselineError::Io { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            result => result,
+        }
+    }
+
+    /// Save the baseline to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), BaselineError> {
+        let io_error = |source| BaselineError::Io {
+            path: path.to_owned(),
+            source,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let json = serde_json::to_string_pretty(self).expect("baselines serialize to JSON");
+        fs::write(path, json + "\n").map_err(io_error)
+    }
+
+    /// Store `report` of a day benchmarked on `input`, replacing any previous report.
+    pub fn insert(&mut self, input: &str, report: Report) {
+        self.0
+            .entry(input.to_owned())
+            .or_default()
+            .insert(report.day, report);
+    }
+
+    /// Report of `day` benchmarked on `input`, if stored.
+    pub fn get(&self, input: &str, day: u8) -> Option<&Report> {
+        self.0.get(input)?.get(&day)
+    }
+}
+
+/// What to do with the results of a benchmark run.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Options {
+    /// Name of the baseline to save results to.
+    pub save: Option<String>,
+    /// Name of the baseline to compare results with.
+    pub compare: Option<String>,
+    /// Percentage a function may slow down by before it's flagged as a regression.
+    pub threshold: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            save: None,
+            compare: None,
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+}
+
+impl Options {
+    /// Compare `reports` of days benchmarked on `input` with the baseline and print the
+    /// comparison table, then save them, as requested.
+    ///
+    /// Returns the number of regressions.
+    pub fn apply(&self, input: &str, reports: &[Report]) -> Result<usize, BaselineError> {
+        let mut regressions = 0;
+        if let Some(name) = &self.compare {
+            let baseline = Baseline::load(&Baseline::path(name))?;
+            let mut table = ComparisonTable::new(self.threshold);
+            for report in reports {
+                match baseline.get(input, report.day) {
+                    Some(baseline) => table.push_reports(baseline, report),
+                    None => eprintln!("baseline `{name}` has no results for day {}", report.day),
+                }
+            }
+            println!("{table}");
+            regressions = table.regressions().count();
+        }
+        if let Some(name) = &self.save {
+            let path = Baseline::path(name);
+            let mut baseline = Baseline::load_or_default(&path)?;
+            reports
+                .iter()
+                .for_each(|report| baseline.insert(input, *report));
+            baseline.save(&path)?;
+        }
+        Ok(regressions)
+    }
+}
+
+/// Median time of a single function before and after a change.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Comparison {
+    pub day: u8,
+    /// Name of the function, as in [`Report::stats`].
+    pub name: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// Compare every function in `current` with `baseline`.
+    pub fn of_reports(baseline: &Report, current: &Report) -> [Self; 3] {
+        let mut current_stats = current.stats().into_iter();
+        baseline.stats().map(|(name, baseline)| {
+            let (_, current_stats) = current_stats.next().expect("reports have the same stats");
+            Comparison {
+                day: current.day,
+                name,
+                baseline: baseline.median,
+                current: current_stats.median,
+            }
+        })
+    }
+
+    /// Change of the median time, in percent. Positive if the function got slower.
+    ///
+    /// Returns `None` if the baseline median is zero, since there is no timing to compare
+    /// with.
+    pub fn change(&self) -> Option<f64> {
+        (!self.baseline.is_zero())
+            .then(|| (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0)
+    }
+
+    /// Whether the function slowed down by more than `threshold` percent.
+    /// Never true without a baseline timing.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Table of [comparisons](Comparison), with regressions beyond a threshold flagged.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ComparisonTable {
+    rows: Vec<Comparison>,
+    /// Percentage a function may slow down by before it's flagged as a regression.
+    pub threshold: f64,
+}
+
+impl ComparisonTable {
+    /// Create an empty table that flags slowdowns beyond `threshold` percent.
+    pub fn new(threshold: f64) -> Self {
+        ComparisonTable {
+            rows: vec![],
+            threshold,
+        }
+    }
+
+    /// Add a row for every function in `current` compared with `baseline`.
+    pub fn push_reports(&mut self, baseline: &Report, current: &Report) {
+        self.rows.extend(Comparison::of_reports(baseline, current));
+    }
+
+    /// Comparisons that are flagged as regressions.
+    pub fn regressions(&self) -> impl Iterator<Item = &Comparison> {
+        self.rows
+            .iter()
+            .filter(|row| row.is_regression(self.threshold))
+    }
+}
+
+impl fmt::Display for ComparisonTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>3}  {:<8} {:>12} {:>12} {:>9}",
+            "day", "function", "baseline", "current", "change"
+        )?;
+        for row in &self.rows {
+            writeln!(f)?;
+            write!(
+                f,
+                "{:>3}  {:<8} {:>12} {:>12}",
+                row.day,
+                row.name,
+                format!("{:.2?}", row.baseline),
+                format!("{:.2?}", row.current),
+            )?;
+            match row.change() {
+                Some(change) => write!(f, " {change:>+8.1}%")?,
+                None => write!(f, " no baseline timing")?,
+            }
+            if row.is_regression(self.threshold) {
+                write!(f, "  regression")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Baseline, ComparisonTable};
+    use crate::{Report, Stats};
+    use std::time::Duration;
+
+    fn report(day: u8, micros: [u64; 3]) -> Report {
+        let [parse, part1, part2] =
+            micros.map(|micros| Stats::new(vec![Duration::from_micros(micros)], 1));
+        Report {
+            day,
+            parse,
+            part1,
+            part2,
+        }
+    }
+
+    /// Verify that only slowdowns beyond the threshold are flagged.
+    #[test]
+    fn regressions() {
+        let mut table = ComparisonTable::new(10.0);
+        table.push_reports(&report(1, [100, 100, 100]), &report(1, [105, 50, 111]));
+        let regressions: Vec<_> = table.regressions().map(|row| (row.day, row.name)).collect();
+        assert_eq!(regressions, [(1, "part2")]);
+        let table = table.to_string();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].ends_with("+5.0%"), "{table}");
+        assert!(lines[2].ends_with("-50.0%"), "{table}");
+        assert!(lines[3].ends_with("+11.0%  regression"), "{table}");
+    }
+
+    /// Verify that a zero baseline median is reported instead of compared.
+    #[test]
+    fn zero_baseline() {
+        let mut table = ComparisonTable::new(10.0);
+        table.push_reports(&report(1, [0, 100, 100]), &report(1, [5, 100, 100]));
+        assert_eq!(table.rows[0].change(), None);
+        assert_eq!(table.regressions().count(), 0);
+        let table = table.to_string();
+        let lines: Vec<_> = table.lines().collect();
+        assert!(lines[1].ends_with(" no baseline timing"), "{table}");
+        assert!(lines[2].ends_with("+0.0%"), "{table}");
+    }
+
+    /// Verify that reports are replaced per day and kept apart per kind of input.
+    #[test]
+    fn save_load() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc-bench-{}", std::process::id()))
+            .join("baseline.json");
+        let mut baseline = Baseline::load_or_default(&path).unwrap();
+        baseline.insert("generated", report(1, [1, 2, 3]));
+        baseline.insert("generated", report(2, [1, 2, 3]));
+        baseline.insert("generated", report(1, [4, 5, 6]));
+        baseline.insert("real", report(1, [7, 8, 9]));
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+        assert_eq!(loaded, baseline);
+        assert_eq!(loaded.get("generated", 1), Some(&report(1, [4, 5, 6])));
+        assert_eq!(loaded.get("real", 1), Some(&report(1, [7, 8, 9])));
+        assert_eq!(loaded.get("real", 2), None);
+    }
+}
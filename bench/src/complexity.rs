@@ -0,0 +1,162 @@
+//! Empirical estimation of the time complexity of solutions.
+//!
+//! A solution is measured on inputs of geometrically increasing sizes. Assuming its
+//! running time follows `t = c * n^k` for input size `n` in bytes, `k` is estimated as the
+//! slope of the least squares line through the measurements on a log-log scale.
+
+use crate::{catch_panic, measure, Config};
+use solution::Solution;
+use std::{fmt, time::Duration};
+
+/// Settings used to measure each input size.
+/// Fewer and shorter samples than the [default](Config::default), since there are many sizes.
+pub const CONFIG: Config = Config {
+    warmup: Duration::from_millis(100),
+    samples: 10,
+    sample_time: Duration::from_millis(10),
+};
+
+/// Median times of a single function at each input size,
+/// or the panic message if the function failed at any size.
+pub type Curve = Result<Vec<Duration>, String>;
+
+/// Measurements of a single day at increasing input sizes.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Complexity {
+    pub day: u8,
+    /// Input sizes, in bytes.
+    pub sizes: Vec<usize>,
+    pub parse: Curve,
+    pub part1: Curve,
+    pub part2: Curve,
+}
+
+impl Complexity {
+    /// Curves of each measured function, labelled.
+    pub fn curves(&self) -> [(&'static str, &Curve); 3] {
+        [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ]
+    }
+
+    /// Estimated exponent of each function that didn't fail, labelled.
+    pub fn exponents(&self) -> impl Iterator<Item = (&'static str, f64)> + '_ {
+        self.curves().into_iter().filter_map(|(name, curve)| {
+            let times = curve.as_ref().ok()?;
+            Some((name, exponent(&self.sizes, times)))
+        })
+    }
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "day {} complexity", self.day)?;
+        write!(f, "{:>10}", "size")?;
+        for (name, _) in self.curves() {
+            write!(f, " {name:>12}")?;
+        }
+        for (i, size) in self.sizes.iter().enumerate() {
+            write!(f, "\n{size:>10}")?;
+            for (_, curve) in self.curves() {
+                match curve {
+                    Ok(times) => write!(f, " {:>12}", format!("{:.2?}", times[i]))?,
+                    Err(_) => write!(f, " {:>12}", "-")?,
+                }
+            }
+        }
+        write!(f, "\n{:>10}", "exponent")?;
+        for (_, curve) in self.curves() {
+            match curve {
+                Ok(times) => write!(f, " {:>12.2}", exponent(&self.sizes, times))?,
+                Err(_) => write!(f, " {:>12}", "failed")?,
+            }
+        }
+        for (name, curve) in self.curves() {
+            if let Err(e) = curve {
+                write!(f, "\n{name} failed: {e}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Slope of the least squares line through `(ln size, ln time)`.
+///
+/// # Panics
+/// Panics if fewer than 2 distinct sizes are provided.
+pub fn exponent(sizes: &[usize], times: &[Duration]) -> f64 {
+    assert_eq!(sizes.len(), times.len());
+    let points: Vec<_> = sizes
+        .iter()
+        .zip(times)
+        .map(|(&size, time)| ((size as f64).ln(), time.as_secs_f64().ln()))
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    assert!(variance > 0.0, "at least 2 distinct sizes are required");
+    covariance / variance
+}
+
+/// Measure parsing and both parts of `S` on each of `inputs`, ordered by size.
+///
+/// A function that panics on any input is not measured on larger inputs,
+/// so an unfinished part doesn't prevent measuring the others.
+pub fn estimate<S: Solution>(config: &Config, inputs: &[Vec<u8>]) -> Complexity {
+    let mut curves: [Curve; 3] = [Ok(vec![]), Ok(vec![]), Ok(vec![])];
+    for input in inputs {
+        let parsed = match catch_panic(|| S::parse(input)) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                curves = [0, 1, 2].map(|_| Err(format!("parsing failed: {e}")));
+                break;
+            }
+        };
+        let [parse, part1, part2] = &mut curves;
+        if let Ok(times) = parse {
+            times.push(measure(config, || S::parse(input)).median);
+        }
+        for (curve, part) in [(part1, S::part1 as fn(&_) -> _), (part2, S::part2)] {
+            let Ok(times) = curve else { continue };
+            match catch_panic(|| part(&parsed)) {
+                Ok(_) => times.push(measure(config, || part(&parsed)).median),
+                Err(e) => *curve = Err(e),
+            }
+        }
+    }
+    let [parse, part1, part2] = curves;
+    Complexity {
+        day: S::DAY,
+        sizes: inputs.iter().map(Vec::len).collect(),
+        parse,
+        part1,
+        part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::exponent;
+    use std::time::Duration;
+
+    /// Verify that exponents of exact power laws are recovered.
+    #[test]
+    fn power_law() {
+        let sizes = [1000, 2000, 4000, 8000];
+        for k in [1.0, 1.5, 2.0] {
+            let times: Vec<_> = sizes
+                .iter()
+                .map(|&n| Duration::from_nanos((n as f64).powf(k) as u64))
+                .collect();
+            let estimate = exponent(&sizes, &times);
+            assert!((estimate - k).abs() < 0.01, "{estimate} != {k}");
+        }
+    }
+}
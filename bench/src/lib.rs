@@ -0,0 +1,400 @@
+//! Benchmark harness for puzzle [solutions](Solution) that works on the stable toolchain.
+//!
+//! Each measured function is first run repeatedly during a warmup period, which also
+//! determines how many iterations fit in a single sample. The function is then timed over
+//! a number of samples, and the time per iteration of each sample is summarized in [`Stats`].
+//!
+//! Results can be saved to and compared with [baselines](baseline), and the growth of the
+//! running time with the input size can be [estimated](complexity).
+
+use serde::{Deserialize, Serialize};
+use solution::Solution;
+use std::{
+    fmt,
+    hint::black_box,
+    panic::{self, AssertUnwindSafe},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+pub mod baseline;
+pub mod complexity;
+
+/// Settings of a benchmark run.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Config {
+    /// How long to run a function before taking samples.
+    pub warmup: Duration,
+    /// Number of samples taken of each function.
+    pub samples: usize,
+    /// Target duration of a single sample.
+    /// Fast functions are run multiple times per sample to reach it.
+    pub sample_time: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            warmup: Duration::from_millis(500),
+            samples: 100,
+            sample_time: Duration::from_millis(10),
+        }
+    }
+}
+
+/// Summary of the samples taken of a single function.
+/// All durations are the time of a single iteration.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Stats {
+    /// Number of samples taken.
+    pub samples: usize,
+    /// Number of iterations per sample.
+    pub iterations: u64,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    /// 95th percentile.
+    #[serde(with = "nanos")]
+    pub p95: Duration,
+    #[serde(with = "nanos")]
+    pub mean: Duration,
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarize the time per iteration of each sample.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    pub fn new(mut samples: Vec<Duration>, iterations: u64) -> Self {
+        assert!(!samples.is_empty(), "at least one sample is required");
+        samples.sort_unstable();
+        Stats {
+            samples: samples.len(),
+            iterations,
+            median: percentile(&samples, 0.5),
+            p95: percentile(&samples, 0.95),
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+            min: samples[0],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "median {:.2?}, p95 {:.2?} ({} samples of {} iterations)",
+            self.median, self.p95, self.samples, self.iterations
+        )
+    }
+}
+
+/// Nearest-rank percentile `p` of the `sorted` samples.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Durations stored as whole nanoseconds.
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub(crate) fn serialize<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(d.as_nanos() as u64)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        u64::deserialize(d).map(Duration::from_nanos)
+    }
+}
+
+/// Measure `f`.
+pub fn measure<T>(config: &Config, mut f: impl FnMut() -> T) -> Stats {
+    let mut run = |iterations: u64| {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            black_box(f());
+        }
+        start.elapsed()
+    };
+
+    // Double the iterations per batch until a batch takes as long as a sample should.
+    let mut iterations = 1;
+    let warmup_start = Instant::now();
+    let mut elapsed = run(iterations);
+    while warmup_start.elapsed() < config.warmup {
+        if elapsed < config.sample_time {
+            iterations *= 2;
+        }
+        elapsed = run(iterations);
+    }
+    let per_iteration = elapsed.as_secs_f64() / iterations as f64;
+    let iterations = ((config.sample_time.as_secs_f64() / per_iteration) as u64).max(1);
+
+    let samples = (0..config.samples.max(1))
+        .map(|_| Duration::from_secs_f64(run(iterations).as_secs_f64() / iterations as f64))
+        .collect();
+    Stats::new(samples, iterations)
+}
+
+/// Call `f`, returning the panic message if it panics.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned())
+    })
+}
+
+/// Benchmark results of a single day.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Report {
+    pub day: u8,
+    /// [`Solution::parse`].
+    pub parse: Stats,
+    /// [`Solution::part1`] on parsed input.
+    pub part1: Stats,
+    /// [`Solution::part2`] on parsed input.
+    pub part2: Stats,
+}
+
+impl Report {
+    /// Stats of each measured function, labelled.
+    pub fn stats(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ]
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, stats)) in self.stats().into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "day {} {name}: {stats}", self.day)?;
+        }
+        Ok(())
+    }
+}
+
+/// Benchmark parsing and both parts of `S` on `input`.
+pub fn bench_solution<S: Solution>(config: &Config, input: &[u8]) -> Report {
+    let parse = measure(config, || S::parse(input));
+    let parsed = S::parse(input);
+    Report {
+        day: S::DAY,
+        parse,
+        part1: measure(config, || S::part1(&parsed)),
+        part2: measure(config, || S::part2(&parsed)),
+    }
+}
+
+/// Arguments of the benchmark targets, see [`main`].
+#[derive(Debug, PartialEq, Clone)]
+struct Args {
+    json: bool,
+    options: baseline::Options,
+    complexity: bool,
+    /// Number of input sizes used by `--complexity`, at least 2 to fit an exponent.
+    steps: u32,
+    max_exponent: Option<f64>,
+}
+
+impl Args {
+    const USAGE: &str = "usage: cargo bench -p <day> -- [--json] [--save-baseline <name>] \
+        [--baseline <name>] [--threshold <percent>] [--complexity] [--steps <n>] \
+        [--max-exponent <k>]";
+
+    /// Read arguments, ignoring unknown ones.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args {
+            json: false,
+            options: baseline::Options::default(),
+            complexity: false,
+            steps: 6,
+            max_exponent: None,
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for `{arg}`"))
+            };
+            match arg.as_str() {
+                "--json" => parsed.json = true,
+                "--save-baseline" => parsed.options.save = Some(value()?),
+                "--baseline" => parsed.options.compare = Some(value()?),
+                "--threshold" => {
+                    parsed.options.threshold = value()?
+                        .parse()
+                        .map_err(|_| "threshold must be a number".to_owned())?
+                }
+                "--complexity" => parsed.complexity = true,
+                "--steps" => {
+                    parsed.steps = value()?
+                        .parse()
+                        .ok()
+                        .filter(|&steps| steps >= 2)
+                        .ok_or_else(|| "steps must be an integer of at least 2".to_owned())?
+                }
+                "--max-exponent" => {
+                    parsed.max_exponent = Some(
+                        value()?
+                            .parse()
+                            .map_err(|_| "max exponent must be a number".to_owned())?,
+                    )
+                }
+                _ => {}
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Entry point of the benchmark targets of a day.
+///
+/// Benchmarks `S` on an input of `size` produced by `generate` and prints the [`Report`].
+/// Accepts the following arguments after `--`:
+///
+/// - `--json` prints the report as JSON instead.
+/// - `--save-baseline <name>` saves the report to the [baseline](baseline) called `name`.
+/// - `--baseline <name>` compares the report with the baseline called `name`.
+/// - `--threshold <percent>` sets the slowdown flagged as a regression by `--baseline`.
+/// - `--complexity` [estimates the complexity](complexity) instead, on inputs from `size`
+///   up to `size * 2^(steps - 1)`.
+/// - `--steps <n>` sets the number of input sizes used by `--complexity` (6 by default,
+///   at least 2).
+/// - `--max-exponent <k>` sets the exponent flagged as too high by `--complexity`.
+///
+/// Other arguments, such as the `--bench` flag passed by `cargo bench`, are ignored.
+/// Fails if any regressions or too high exponents are found, or if an argument is invalid.
+pub fn main<S: Solution>(generate: impl Fn(usize) -> String, size: usize) -> ExitCode {
+    let Args {
+        json,
+        options,
+        complexity,
+        steps,
+        max_exponent,
+    } = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{}", Args::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    if complexity {
+        let inputs: Vec<_> = (0..steps)
+            .map(|step| generate(size << step).into_bytes())
+            .collect();
+        let complexity = complexity::estimate::<S>(&complexity::CONFIG, &inputs);
+        println!("{complexity}");
+        let too_high = complexity
+            .exponents()
+            .filter(|&(name, exponent)| {
+                let too_high = max_exponent.is_some_and(|max| exponent > max);
+                if too_high {
+                    eprintln!(
+                        "{name} exponent {exponent:.2} exceeds {}",
+                        max_exponent.unwrap()
+                    );
+                }
+                too_high
+            })
+            .count();
+        return match too_high {
+            0 => ExitCode::SUCCESS,
+            _ => ExitCode::FAILURE,
+        };
+    }
+    let report = bench_solution::<S>(&Config::default(), generate(size).as_bytes());
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("reports serialize to JSON")
+        );
+    } else {
+        println!("{report}");
+    }
+    match options.apply("generated", &[report]) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(regressions) => {
+            eprintln!("{regressions} regression(s) beyond {}%", options.threshold);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{measure, Args, Config, Stats};
+    use std::time::Duration;
+
+    /// Verify that invalid values are rejected, and unknown arguments ignored.
+    #[test]
+    fn args() {
+        let parse = |args: &[&str]| Args::parse(args.iter().map(|&arg| arg.to_owned()));
+        let args = parse(&["--bench", "--complexity", "--steps", "2"]).unwrap();
+        assert!(args.complexity);
+        assert_eq!(args.steps, 2);
+        for steps in ["0", "1", "-1", "many"] {
+            assert!(parse(&["--steps", steps]).is_err(), "{steps}");
+        }
+        assert!(parse(&["--steps"]).is_err());
+        assert!(parse(&["--threshold", "high"]).is_err());
+        assert!(parse(&["--max-exponent", "x"]).is_err());
+    }
+
+    /// Verify that percentiles are taken with the nearest-rank method.
+    #[test]
+    fn stats() {
+        let samples = (1..=100).rev().map(Duration::from_micros).collect();
+        let stats = Stats::new(samples, 1);
+        assert_eq!(stats.median, Duration::from_micros(50));
+        assert_eq!(stats.p95, Duration::from_micros(95));
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.max, Duration::from_micros(100));
+        assert_eq!(stats.mean, Duration::from_nanos(50_500));
+        let stats = Stats::new(vec![Duration::from_micros(7)], 1);
+        assert_eq!((stats.median, stats.p95), (stats.min, stats.max));
+    }
+
+    /// Verify that fast functions are run multiple times per sample.
+    #[test]
+    fn measure_batches() {
+        let config = Config {
+            warmup: Duration::from_millis(20),
+            samples: 5,
+            sample_time: Duration::from_millis(1),
+        };
+        let stats = measure(&config, || std::hint::black_box(1) + 1);
+        assert_eq!(stats.samples, 5);
+        assert!(stats.iterations > 1);
+        assert!(stats.min <= stats.median && stats.median <= stats.p95);
+    }
+
+    /// Verify that stats survive a round trip through JSON.
+    #[test]
+    fn json() {
+        let samples = vec![Duration::from_nanos(3), Duration::from_nanos(4)];
+        let stats = Stats::new(samples, 1000);
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.contains(r#""median":3,"#), "{json}");
+        assert_eq!(serde_json::from_str::<Stats>(&json).unwrap(), stats);
+    }
+}
@@ -0,0 +1,10 @@
+//! Benchmarks of day 1 on generated input.
+
+use day1::Day1;
+use generate::{Seeded, DEFAULT_SEED};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let generate = |size| generate::day1::input(&mut Seeded::from_env_or(DEFAULT_SEED), size);
+    bench::main::<Day1>(generate, generate::day1::DEFAULT_SIZE)
+}
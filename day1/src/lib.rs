@@ -0,0 +1,194 @@
+//! Day 1: Historian Hysteria.
+//!
+//! The input is two lists of location IDs, one pair per line. [`parse_lists`] reads them,
+//! [`list_distance`] solves part 1 and [`similarity_score`] solves part 2.
+//! [`Day1`] wraps them in the [`Solution`] interface with [`ItemInt`] items, while [`solve`]
+//! takes the item type at runtime, e.g. as selected by [`int_type`].
+
+use abs_diff::AbsDiff;
+use itertools::Itertools as _;
+use num::{cast::AsPrimitive, Integer};
+use rayon::iter::{IntoParallelRefIterator as _, ParallelIterator as _};
+use solution::{
+    int::{Bounds, IntType},
+    Solution,
+};
+use std::{
+    fmt::{Debug, Display},
+    iter::Sum,
+    str::FromStr,
+};
+
+/// Functions that operate on the lists require items to satisfy this trait bound.
+pub trait Item: Integer + Copy + 'static {}
+impl<T> Item for T where T: Integer + Copy + 'static {}
+
+/// Integer type list items are stored/parsed as.
+pub type ItemInt = u32;
+
+/// Solution for day 1.
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = (Vec<ItemInt>, Vec<ItemInt>);
+
+    fn parse(input: &[u8]) -> Self::Input {
+        let input = std::str::from_utf8(input).expect("input must be valid UTF-8");
+        parse_lists(input)
+    }
+
+    fn part1((left, right): &Self::Input) -> String {
+        list_distance(&mut left.clone(), &mut right.clone()).to_string()
+    }
+
+    fn part2((left, right): &Self::Input) -> String {
+        similarity_score(left, right).to_string()
+    }
+}
+
+/// Narrowest item type that holds every item in an input with `bounds` and both answers.
+/// Returns `None` if no type can.
+pub fn int_type(bounds: &Bounds) -> Option<IntType> {
+    let pairs = bounds.count as i128 / 2;
+    // Distances are at most `max - min`. A similarity term is an item times the number of
+    // times it appears in the right list, so it is at most `pairs` times the item.
+    let distance = pairs.checked_mul(bounds.max - bounds.min)?;
+    let magnitude = bounds.min.abs().max(bounds.max.abs());
+    let similarity = pairs.checked_mul(pairs)?.checked_mul(magnitude)?;
+    let min = if bounds.min < 0 { -similarity } else { 0 };
+    IntType::narrowest(min, distance.max(similarity))
+}
+
+/// Solve both parts with items of type `int`.
+///
+/// # Panics
+/// Panics if an item doesn't fit in `int`.
+pub fn solve(input: &str, int: IntType) -> [String; 2] {
+    match int {
+        IntType::U16 => solve_as::<u16>(input),
+        IntType::U32 => solve_as::<u32>(input),
+        IntType::U64 => solve_as::<u64>(input),
+        IntType::I64 => solve_as::<i64>(input),
+    }
+}
+
+/// Solve both parts with items of type `T`.
+fn solve_as<T>(input: &str) -> [String; 2]
+where
+    T: Item + Sync + Send + Sum + FromStr + Display,
+    <T as FromStr>::Err: Debug,
+    ItemInt: AsPrimitive<T>,
+    usize: AsPrimitive<T>,
+{
+    let (mut left, mut right) = parse_lists::<T>(input);
+    let similarity = similarity_score(&left, &right);
+    let distance = list_distance(&mut left, &mut right);
+    [distance.to_string(), similarity.to_string()]
+}
+
+/// Parse input into two lists.
+///
+/// # Panics
+/// Panics if an item is not a valid `T`, or the lists don't have the same length.
+pub fn parse_lists<T>(input: &str) -> (Vec<T>, Vec<T>)
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    let mut left = vec![];
+    let mut right = vec![];
+    input
+        .split_ascii_whitespace()
+        .map(|s| s.trim().parse().expect("items must be parsable to T"))
+        .chunks(2)
+        .into_iter()
+        .for_each(|mut chunk| {
+            left.push(chunk.next().expect("doesn't return empty chunks"));
+            right.push(chunk.next().expect("lists must have same length"));
+        });
+    (left, right)
+}
+
+/// Compute the "list distance" between two lists.
+/// Sorts both lists first.
+pub fn list_distance<T>(left: &mut [T], right: &mut [T]) -> T
+where
+    T: Item,
+    ItemInt: AsPrimitive<T>,
+{
+    left.sort_unstable();
+    right.sort_unstable();
+    left.iter_mut()
+        .zip(right)
+        .fold(0.as_(), |acc, (l, r)| acc + l.abs_diff(*r))
+}
+
+/// Compute the "similarity score" between two lists.
+pub fn similarity_score<T>(left: &[T], right: &[T]) -> T
+where
+    T: Item + Sync + Send + Sum,
+    usize: AsPrimitive<T>,
+{
+    // Compute bound - use parallelism to speed up.
+    left.par_iter()
+        .map(|l| right.iter().filter(|r| l == *r).count().as_() * *l)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{int_type, parse_lists, similarity_score, solve, ItemInt};
+    use generate::Seeded;
+    use solution::int::{Bounds, IntType};
+    use std::collections::HashMap;
+
+    solution::sample_tests!(crate::Day1);
+
+    /// Compare part 2 with a counting implementation on generated input.
+    #[test]
+    fn similarity_score_generated() {
+        let mut rng = Seeded::from_env();
+        let input = generate::day1::input(&mut rng, 1000);
+        let (left, right) = parse_lists::<ItemInt>(&input);
+        let mut counts = HashMap::new();
+        right
+            .iter()
+            .for_each(|r| *counts.entry(r).or_insert(0) += 1);
+        let expected: ItemInt = left.iter().map(|l| l * counts.get(l).unwrap_or(&0)).sum();
+        assert_eq!(similarity_score(&left, &right), expected);
+    }
+
+    /// Verify that every item type that holds the answers gives the same answers.
+    #[test]
+    fn int_types() {
+        let input = "3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n";
+        let bounds = Bounds::of(input).unwrap();
+        assert_eq!(int_type(&bounds), Some(IntType::U16));
+        for int in IntType::ALL {
+            assert_eq!(solve(input, int), ["11", "31"], "{int}");
+        }
+        let negative = "-3 4\n-1 -3\n";
+        let bounds = Bounds::of(negative).unwrap();
+        assert_eq!(int_type(&bounds), Some(IntType::I64));
+        assert_eq!(solve(negative, IntType::I64), ["5", "-3"]);
+        let large = "60000 60000\n60000 60000\n";
+        assert_eq!(int_type(&Bounds::of(large).unwrap()), Some(IntType::U32));
+    }
+
+    /// Verify that the selected type holds the answers of an input with repeated items.
+    #[test]
+    fn int_type_repeated() {
+        let input = "200 200\n".repeat(300);
+        let int = int_type(&Bounds::of(&input).unwrap()).unwrap();
+        let answers = solve(&input, IntType::I64);
+        assert_eq!(answers, ["0", "18000000"]);
+        let holds = |answer: &String| {
+            let answer = answer.parse().unwrap();
+            int.holds(answer, answer)
+        };
+        assert!(answers.iter().all(holds), "{int}");
+        assert_eq!(solve(&input, int), answers, "{int}");
+    }
+}
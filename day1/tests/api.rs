@@ -0,0 +1,25 @@
+//! Tests of the public API of day 1.
+
+use day1::{list_distance, parse_lists, similarity_score, Day1};
+use solution::Solution;
+
+const SAMPLE: &str = include_str!("../sample_input.txt");
+
+/// Verify that both parts with an item type other than the one used by [`Day1`] match the
+/// solution, and that the lists are sorted to compute the distance.
+#[test]
+fn generic_items() {
+    let (mut left, mut right) = parse_lists::<u64>(SAMPLE);
+    assert_eq!(left.len(), SAMPLE.lines().count());
+    assert_eq!(right.len(), left.len());
+    let input = Day1::parse(SAMPLE.as_bytes());
+    assert_eq!(
+        similarity_score(&left, &right).to_string(),
+        Day1::part2(&input)
+    );
+    assert_eq!(
+        list_distance(&mut left, &mut right).to_string(),
+        Day1::part1(&input)
+    );
+    assert!(left.is_sorted() && right.is_sorted());
+}
@@ -0,0 +1,10 @@
+//! Benchmarks of day 2 on generated input.
+
+use day2::Day2;
+use generate::{Seeded, DEFAULT_SEED};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let generate = |size| generate::day2::input(&mut Seeded::from_env_or(DEFAULT_SEED), size);
+    bench::main::<Day2>(generate, generate::day2::DEFAULT_SIZE)
+}
@@ -0,0 +1,77 @@
+//! Benchmarks of day 2 with nested reports (one allocation per report) and flat [`Reports`],
+//! on generated input.
+//!
+//! Throughput is the size of the input processed per second, so parsing and counting are
+//! comparable. Arguments are ignored, so `cargo bench -p day2 -- <options>` only applies
+//! the options to the day benchmark.
+
+use day2::{parse_reports, strict, tolerant, LevelInt, Reports};
+use generate::{Seeded, DEFAULT_SEED};
+use rayon::iter::{IntoParallelRefIterator as _, ParallelIterator as _};
+
+fn main() {
+    let config = bench::Config::default();
+    let mut rng = Seeded::from_env_or(DEFAULT_SEED);
+    let input = generate::day2::input(&mut rng, generate::day2::DEFAULT_SIZE);
+    let nested = parse_reports::<LevelInt>(&input);
+    let flat = Reports::<LevelInt>::parse(input.as_bytes()).unwrap();
+    let measurements = [
+        (
+            "nested",
+            "parse",
+            bench::measure(&config, || parse_reports::<LevelInt>(&input)),
+        ),
+        (
+            "flat",
+            "parse",
+            bench::measure(&config, || Reports::<LevelInt>::parse(input.as_bytes())),
+        ),
+        (
+            "nested",
+            "strict",
+            bench::measure(&config, || {
+                nested
+                    .iter()
+                    .filter(|report| strict::report_safety(report))
+                    .count()
+            }),
+        ),
+        (
+            "flat",
+            "strict",
+            bench::measure(&config, || strict::safe_reports_count(&flat)),
+        ),
+        (
+            "nested",
+            "tolerant",
+            bench::measure(&config, || {
+                nested
+                    .par_iter()
+                    .filter(|report| tolerant::report_safety(report))
+                    .count()
+            }),
+        ),
+        (
+            "flat",
+            "tolerant",
+            bench::measure(&config, || tolerant::safe_reports_count(&flat)),
+        ),
+    ];
+    println!(
+        "day 2 layouts ({} reports, {} bytes)",
+        flat.len(),
+        input.len()
+    );
+    println!(
+        "{:<8} {:<10} {:>12} {:>12}",
+        "layout", "function", "median", "throughput"
+    );
+    for (layout, function, stats) in measurements {
+        let throughput = input.len() as f64 / stats.median.as_secs_f64() / 1e6;
+        println!(
+            "{layout:<8} {function:<10} {:>12} {:>9.1} MB/s",
+            format!("{:.2?}", stats.median),
+            throughput
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! Benchmarks of the scalar and [vectorised](strict::simd) versions of part 1, on the
+//! default generated input and on generated inputs with different shares of safe reports.
+//!
+//! The scalar version stops checking a report at its first invalid difference, while the
+//! vectorised version checks every difference, so it gains the most on mostly safe reports.
+//! Arguments are ignored, so `cargo bench -p day2 -- <options>` only applies the options
+//! to the day benchmark.
+
+use day2::{
+    strict, {LevelInt, Reports},
+};
+use generate::{
+    day2::{labelled, Label},
+    Seeded, DEFAULT_SEED,
+};
+
+fn main() {
+    let config = bench::Config::default();
+    let mut rng = Seeded::from_env_or(DEFAULT_SEED);
+    println!("day 2 part 1 ({} reports)", generate::day2::DEFAULT_SIZE);
+    println!("{:>7} {:>12} {:>12}", "safe", "scalar", "simd");
+    let default = generate::day2::input(&mut rng, generate::day2::DEFAULT_SIZE);
+    let labelled = [1, 50, 100].map(|safe| {
+        let weights = [(Label::Safe, safe), (Label::Unsafe, 100 - safe)];
+        let input = labelled(&mut rng, generate::day2::DEFAULT_SIZE, 5..=8, &weights).input;
+        (format!("{safe}%"), input)
+    });
+    for (name, input) in [("default".to_owned(), default)]
+        .into_iter()
+        .chain(labelled)
+    {
+        let reports = Reports::<LevelInt>::parse(input.as_bytes()).unwrap();
+        let scalar = bench::measure(&config, || strict::safe_reports_count(&reports));
+        let simd = bench::measure(&config, || strict::simd::safe_reports_count(&reports));
+        println!(
+            "{:>7} {:>12} {:>12}",
+            name,
+            format!("{:.2?}", scalar.median),
+            format!("{:.2?}", simd.median)
+        );
+    }
+}
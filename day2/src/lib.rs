@@ -0,0 +1,174 @@
+//! Day 2: Red-Nosed Reports.
+//!
+//! The input is a list of reports, one per line, each a list of levels. [`Reports::parse`]
+//! reads them into a single buffer, [`strict`] solves part 1 and [`tolerant`] solves part 2.
+//! [`Day2`] wraps them in the [`Solution`] interface with [`LevelInt`] levels, while [`solve`]
+//! takes the level type at runtime, e.g. as selected by [`int_type`].
+
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+use num::cast::AsPrimitive;
+use num::{CheckedAdd, CheckedMul, CheckedSub, Integer};
+use solution::{
+    int::{Bounds, IntType},
+    Solution,
+};
+use std::{fmt::Debug, str::FromStr};
+
+pub mod online;
+pub mod removal;
+pub mod reports;
+pub mod strict;
+pub mod tolerant;
+
+pub use reports::Reports;
+
+/// Functions that operate on reports require levels to satisfy this trait bound.
+pub trait Level: Integer + CheckedAdd + CheckedSub + CheckedMul + Copy + 'static {}
+impl<T> Level for T where T: Integer + CheckedAdd + CheckedSub + CheckedMul + Copy + 'static {}
+
+/// Integer type levels are stored/parsed as.
+pub type LevelInt = i32;
+
+/// Solution for day 2.
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Reports<LevelInt>;
+
+    fn parse(input: &[u8]) -> Self::Input {
+        Reports::parse(input).unwrap_or_else(|e| panic!("invalid input: {e}"))
+    }
+
+    fn part1(reports: &Self::Input) -> String {
+        strict::safe_reports_count(reports).to_string()
+    }
+
+    fn part2(reports: &Self::Input) -> String {
+        tolerant::safe_reports_count(reports).to_string()
+    }
+}
+
+/// Narrowest level type that holds every level in an input with `bounds`.
+/// Returns `None` if no type can.
+pub fn int_type(bounds: &Bounds) -> Option<IntType> {
+    IntType::narrowest(bounds.min, bounds.max)
+}
+
+/// Solve both parts with levels of type `int`.
+///
+/// # Panics
+/// Panics if a level doesn't fit in `int`.
+pub fn solve(input: &str, int: IntType) -> [String; 2] {
+    match int {
+        IntType::U16 => solve_as::<u16>(input),
+        IntType::U32 => solve_as::<u32>(input),
+        IntType::U64 => solve_as::<u64>(input),
+        IntType::I64 => solve_as::<i64>(input),
+    }
+}
+
+/// Solve both parts with levels of type `T`.
+fn solve_as<T>(input: &str) -> [String; 2]
+where
+    T: Level + Sync,
+    LevelInt: AsPrimitive<T>,
+{
+    let reports = Reports::<T>::parse(input.as_bytes()).unwrap_or_else(|e| panic!("{e}"));
+    [
+        strict::safe_reports_count(&reports).to_string(),
+        tolerant::safe_reports_count(&reports).to_string(),
+    ]
+}
+
+/// Parse input into reports, with one allocation per report.
+///
+/// Slower than [`Reports::parse`], but accepts any level type that can be parsed from a
+/// string. Nested reports can be [collected](FromIterator) into [`Reports`].
+///
+/// # Panics
+/// Panics if a level is not a valid `T`.
+pub fn parse_reports<T>(input: &str) -> Vec<Vec<T>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    input
+        .lines()
+        .map(|line| {
+            line.split_ascii_whitespace()
+                .map(|x| x.parse().expect("levels must be parsable to T"))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{int_type, removal, solve, strict, tolerant, LevelInt, Reports};
+    use generate::Seeded;
+    use solution::int::{Bounds, IntType};
+
+    solution::sample_tests!(crate::Day2);
+
+    /// Verify that the narrowest level type holding levels and their differences is selected.
+    #[test]
+    fn int_types() {
+        let int = |input| int_type(&Bounds::of(input).unwrap());
+        assert_eq!(int("7 6 4 2 1\n1 3 6 7 9"), Some(IntType::U16));
+        assert_eq!(int("1 70000 70001"), Some(IntType::U32));
+        assert_eq!(int("1 -2 -3"), Some(IntType::I64));
+        assert_eq!(solve("1 -2 -3 -4\n-1 0 1 5", IntType::I64), ["1", "2"]);
+    }
+
+    /// Verify that tolerating a bad level never makes fewer reports safe, on generated input.
+    #[test]
+    fn tolerant_generated() {
+        let mut rng = Seeded::from_env();
+        let reports =
+            Reports::<LevelInt>::parse(generate::day2::input(&mut rng, 1000).as_bytes()).unwrap();
+        let strict = strict::safe_reports_count(&reports);
+        let tolerant = tolerant::safe_reports_count(&reports);
+        assert!(0 < strict && strict <= tolerant && tolerant < reports.len());
+    }
+
+    /// Verify both parts against the planted label of every generated report.
+    #[test]
+    fn labelled() {
+        use generate::day2::Label;
+        let mut rng = Seeded::from_env();
+        let weights = [(Label::Safe, 1), (Label::Tolerated, 1), (Label::Unsafe, 1)];
+        let labelled = generate::day2::labelled(&mut rng, 3000, 5..=8, &weights);
+        let reports = Reports::<LevelInt>::parse(labelled.input.as_bytes()).unwrap();
+        assert_eq!(
+            strict::safe_reports_count(&reports),
+            labelled.strict_count()
+        );
+        assert_eq!(
+            tolerant::safe_reports_count(&reports),
+            labelled.tolerant_count()
+        );
+        let with_k = |k| removal::safe_reports_count_with_k(&reports, k);
+        assert_eq!(with_k(0), labelled.strict_count());
+        assert_eq!(with_k(1), labelled.tolerant_count());
+        for int in IntType::ALL {
+            let expected = [labelled.strict_count(), labelled.tolerant_count()];
+            assert_eq!(
+                solve(&labelled.input, int),
+                expected.map(|n| n.to_string()),
+                "{int}"
+            );
+        }
+        for (report, label) in reports.iter().zip(&labelled.labels) {
+            let strict = strict::report_safety(report);
+            let tolerant = tolerant::report_safety(report);
+            assert_eq!(
+                (strict, tolerant),
+                (*label == Label::Safe, *label != Label::Unsafe),
+                "{report:?}"
+            );
+        }
+    }
+}
@@ -1,100 +1,55 @@
-#![feature(test)]
+use solution::int::{self, IntType};
 
-// Benchmarking utility.
-extern crate test;
-
-use num::Integer;
-
-pub(crate) mod strict;
-mod tolerant;
-
-/// Functions that operate on reports require levels to satisfy this trait bound.
-trait Level: Integer + Copy + 'static {}
-impl<T> Level for T where T: Integer + Copy + 'static {}
+/// Command line arguments.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+struct Args {
+    /// Selected with `--backend <name>`. Selected from the input if not set.
+    backend: Option<IntType>,
+}
 
-/// Integer type levels are stored/parsed as.
-type LevelInt = u32;
+impl Args {
+    const USAGE: &str = "usage: day2 [--backend <u16|u32|u64|i64>]";
+
+    /// Read arguments from the command line.
+    fn from_env() -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--backend" => {
+                    parsed.backend = Some(
+                        args.next()
+                            .ok_or_else(|| "missing value for `--backend`".to_owned())?
+                            .parse()?,
+                    )
+                }
+                _ => return Err(format!("unexpected argument `{arg}`")),
+            }
+        }
+        Ok(parsed)
+    }
+}
 
 /// Solve both parts and print results to standard output.
 fn main() {
+    let args = match Args::from_env() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{}", Args::USAGE);
+            std::process::exit(2);
+        }
+    };
     let input = input::stdin_file();
-    let reports = parse_reports(&input);
-    let count_strict = strict::safe_reports_count(&reports);
-    let count_tolerant = tolerant::safe_reports_count(&reports);
-    println!("safe reports (strict): {count_strict}");
-    println!("safe reports (tolerant): {count_tolerant}");
-}
-
-/// Parse input into reports.
-fn parse_reports(input: &str) -> Vec<Vec<LevelInt>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|x| x.parse().unwrap())
-                .collect()
-        })
-        .collect()
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{parse_reports, strict, tolerant};
-    use itertools::Itertools;
-    use rand::Rng;
-
-    pub(crate) fn generate_input(reports: usize) -> String {
-        let rand_level = || rand::thread_rng().gen_range(0..=110);
-        let rand_level_shift = || rand::thread_rng().gen_range(-5..=5i32);
-        let rand_report_len = || rand::thread_rng().gen_range(4..=8);
-        let reports = (0..reports)
-            .map(|_| {
-                let len = rand_report_len();
-                let shifts = (0..len)
-                    .into_iter()
-                    .map(|_| rand_level_shift())
-                    .collect_vec();
-                let mut level = shifts
-                    .iter()
-                    .fold(rand_level(), |acc, shift| acc + shift.abs());
-                (0..len)
-                    .into_iter()
-                    .map(|i| {
-                        let shift = shifts[i];
-                        let old_level = level;
-                        let new_level = level as i32 + shift;
-                        level = new_level;
-                        old_level
-                    })
-                    .collect_vec()
-            })
-            .collect_vec();
-        let mut input = String::new();
-        reports
-            .into_iter()
-            .for_each(|report| input.push_str(&format!("{}\n", report.iter().join(" "))));
-        input
-    }
-
-    #[bench]
-    fn bench_read_reports(c: &mut test::Bencher) {
-        let input = generate_input(1000);
-        c.iter(|| parse_reports(&input));
-    }
-
-    /// Test part 1 on sample input.
-    #[test]
-    fn part1() {
-        let reports = parse_reports(include_str!("../sample_input.txt"));
-        let output = strict::safe_reports_count(&reports);
-        assert_eq!(output, 2);
-    }
-
-    /// Test part 2 on sample input.
-    #[test]
-    fn part2() {
-        let reports = parse_reports(include_str!("../sample_input.txt"));
-        let output = tolerant::safe_reports_count(&reports);
-        assert_eq!(output, 4);
-    }
+    let int = match int::select(&input, args.backend, day2::int_type) {
+        Ok(int) => int,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+    eprintln!("solving with `{int}`");
+    let [part1, part2] = day2::solve(&input, int);
+    println!("safe reports (strict): {part1}");
+    println!("safe reports (tolerant): {part2}");
 }
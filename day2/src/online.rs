@@ -0,0 +1,219 @@
+//! Validation of a report whose levels arrive one at a time.
+//!
+//! [`Validator`] keeps constant state per report, so each level is checked in `O(1)` time
+//! without storing the report. For each direction of the [strict](crate::strict) rules it
+//! tracks whether the levels so far are valid:
+//! - without removing any level,
+//! - after removing a single level before the last one,
+//! - after removing the last level.
+//!
+//! A new level follows the last level, unless the last level is the one removed, in which
+//! case it follows the level before the last. So only those two levels are kept, and every
+//! prefix of the report is also checked by the [tolerant](crate::tolerant) rules.
+
+use crate::{
+    strict::{decreasing, increasing},
+    Level, LevelInt,
+};
+use num::cast::AsPrimitive;
+
+/// Safety of the levels received so far.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Status {
+    /// Safe without removing any level (part 1).
+    pub strict: bool,
+    /// Safe after removing at most one level (part 2).
+    pub tolerant: bool,
+}
+
+/// Validity of the levels so far in a single direction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Direction {
+    /// Valid without removing any level.
+    whole: bool,
+    /// Valid after removing a single level before the last one.
+    removed_before_last: bool,
+    /// Valid after removing the last level.
+    removed_last: bool,
+}
+
+impl Direction {
+    /// Validity of a report without levels.
+    const EMPTY: Direction = Direction {
+        whole: true,
+        removed_before_last: false,
+        removed_last: false,
+    };
+
+    /// Validity after receiving `level`, given the levels received before it.
+    /// `adjacent` tells whether a level can follow another in this direction.
+    fn push<T: Copy>(
+        self,
+        level: T,
+        last: Option<T>,
+        before_last: Option<T>,
+        adjacent: fn(T, T) -> bool,
+    ) -> Self {
+        let follows = |previous: Option<T>| previous.is_none_or(|p| adjacent(p, level));
+        Direction {
+            whole: self.whole && follows(last),
+            // Either a level was already removed, or the last level is removed now.
+            removed_before_last: (self.removed_before_last && follows(last))
+                || (self.removed_last && follows(before_last)),
+            removed_last: self.whole,
+        }
+    }
+}
+
+/// Validator of a report that receives its levels one at a time.
+///
+/// # Examples
+/// ```
+/// use day2::online::Validator;
+///
+/// let mut validator = Validator::new();
+/// let statuses: Vec<_> = [1, 3, 2, 4, 5]
+///     .into_iter()
+///     .map(|level| validator.push(level))
+///     .map(|status| (status.strict, status.tolerant))
+///     .collect();
+/// assert_eq!(
+///     statuses,
+///     [(false, false), (true, true), (false, true), (false, true), (false, true)]
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Validator<T> {
+    /// Number of levels received.
+    len: usize,
+    /// Last level received.
+    last: Option<T>,
+    /// Level received before the last one.
+    before_last: Option<T>,
+    increasing: Direction,
+    decreasing: Direction,
+}
+
+impl<T> Validator<T>
+where
+    T: Level,
+    LevelInt: AsPrimitive<T>,
+{
+    /// Create a validator of a report without levels.
+    pub fn new() -> Self {
+        Validator {
+            len: 0,
+            last: None,
+            before_last: None,
+            increasing: Direction::EMPTY,
+            decreasing: Direction::EMPTY,
+        }
+    }
+
+    /// Receive the next level of the report, and return the safety of the levels so far.
+    pub fn push(&mut self, level: T) -> Status {
+        let (last, before_last) = (self.last, self.before_last);
+        self.increasing = self.increasing.push(level, last, before_last, increasing);
+        self.decreasing = self.decreasing.push(level, last, before_last, decreasing);
+        self.before_last = self.last.replace(level);
+        self.len += 1;
+        self.status()
+    }
+
+    /// Safety of the levels received so far.
+    ///
+    /// Reports with fewer than 2 levels are not safe, even after removing a level.
+    pub fn status(&self) -> Status {
+        let directions = [self.increasing, self.decreasing];
+        Status {
+            strict: self.len >= 2 && directions.iter().any(|d| d.whole),
+            tolerant: (self.len >= 2 && directions.iter().any(|d| d.whole))
+                || (self.len >= 3
+                    && directions
+                        .iter()
+                        .any(|d| d.removed_before_last || d.removed_last)),
+        }
+    }
+
+    /// Number of levels received.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no level was received yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> Default for Validator<T>
+where
+    T: Level,
+    LevelInt: AsPrimitive<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Status, Validator};
+    use crate::{strict, tolerant, LevelInt, Reports};
+    use generate::{
+        day2::{labelled, Label},
+        Seeded,
+    };
+    use rand::Rng;
+
+    /// Compare the status after every level with both parts on the prefix so far.
+    fn check_prefixes(report: &[LevelInt]) {
+        let mut validator = Validator::new();
+        for (len, &level) in report.iter().enumerate() {
+            let prefix = &report[..=len];
+            let expected = Status {
+                strict: strict::report_safety(prefix),
+                tolerant: tolerant::report_safety(prefix),
+            };
+            assert_eq!(validator.push(level), expected, "{prefix:?}");
+        }
+        assert_eq!(validator.len(), report.len());
+    }
+
+    /// Verify every prefix of random reports with small steps, including repeated levels.
+    #[test]
+    fn prefixes() {
+        let mut rng = Seeded::from_env();
+        for _ in 0..2000 {
+            let len = rng.gen_range(0..=10);
+            let mut level: LevelInt = rng.gen_range(-10..=10);
+            let report: Vec<_> = (0..len)
+                .map(|_| {
+                    level += rng.gen_range(-4..=4);
+                    level
+                })
+                .collect();
+            check_prefixes(&report);
+        }
+    }
+
+    /// Verify every prefix of generated reports with planted labels.
+    #[test]
+    fn labelled_prefixes() {
+        let mut rng = Seeded::from_env();
+        let weights = [(Label::Safe, 1), (Label::Tolerated, 1), (Label::Unsafe, 1)];
+        let labelled = labelled(&mut rng, 1000, 5..=8, &weights);
+        let reports = Reports::<LevelInt>::parse(labelled.input.as_bytes()).unwrap();
+        reports.iter().for_each(check_prefixes);
+    }
+
+    /// Verify that differences that don't fit in the level type are not valid.
+    #[test]
+    fn extremes() {
+        let mut validator = Validator::new();
+        validator.push(i32::MIN);
+        assert!(!validator.push(i32::MAX).strict);
+        assert!(validator.push(i32::MAX - 2).tolerant);
+        assert!(!validator.push(i32::MIN).tolerant);
+    }
+}
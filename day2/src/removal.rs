@@ -0,0 +1,170 @@
+//! Minimum number of levels to remove from a report to make it safe.
+//!
+//! Removing levels keeps the rest in order, so the levels kept form a subsequence of the
+//! report that is safe by the [strict](crate::strict) rules. The fewest levels are removed
+//! by keeping the longest such subsequence, found separately for increasing and decreasing
+//! levels in `O(n²)` for a report of `n` levels.
+//!
+//! [`tolerant`](crate::tolerant) reports are those with at most one level to remove.
+
+use crate::{
+    strict::{decreasing, increasing},
+    Level, LevelInt, Reports,
+};
+use num::cast::AsPrimitive;
+use rayon::iter::ParallelIterator as _;
+
+/// Indices of the fewest levels to remove from `report` to make it safe, in increasing order.
+/// If several sets of levels are equally small, one of them is returned.
+///
+/// Returns `None` if no removal can make the report safe, i.e. if it has no two levels
+/// that can be adjacent in a safe report.
+pub fn min_removal<T>(report: &[T]) -> Option<Vec<usize>>
+where
+    T: Level,
+    LevelInt: AsPrimitive<T>,
+{
+    let longest = longest_safe_subsequence(report)?;
+    let mut kept = longest.into_iter().peekable();
+    Some(
+        (0..report.len())
+            .filter(|&i| kept.next_if_eq(&i).is_none())
+            .collect(),
+    )
+}
+
+/// Number of the fewest levels to remove from `report` to make it safe.
+/// Returns `None` if no removal can make the report safe.
+pub fn min_removal_count<T>(report: &[T]) -> Option<usize>
+where
+    T: Level,
+    LevelInt: AsPrimitive<T>,
+{
+    let longest = [increasing, decreasing]
+        .into_iter()
+        .filter_map(|adjacent| {
+            let (lengths, _) = subsequence_lengths(report, adjacent);
+            lengths.into_iter().max()
+        })
+        .max()?;
+    (longest >= 2).then(|| report.len() - longest)
+}
+
+/// Count the number of reports that are safe after removing at most `k` levels.
+pub fn safe_reports_count_with_k<T>(reports: &Reports<T>, k: usize) -> usize
+where
+    T: Level + Sync,
+    LevelInt: AsPrimitive<T>,
+{
+    reports
+        // Reports are independent, and each takes time quadratic in its length.
+        .par_iter()
+        .filter(|report| min_removal_count(report).is_some_and(|count| count <= k))
+        .count()
+}
+
+/// Length of the longest subsequence of `report` ending at each level in which every level
+/// can follow the previous one, and the index of the previous level in that subsequence.
+fn subsequence_lengths<T: Copy>(
+    report: &[T],
+    adjacent: fn(T, T) -> bool,
+) -> (Vec<usize>, Vec<Option<usize>>) {
+    let mut lengths = vec![1; report.len()];
+    let mut previous = vec![None; report.len()];
+    for i in 0..report.len() {
+        for j in 0..i {
+            if adjacent(report[j], report[i]) && lengths[j] + 1 > lengths[i] {
+                lengths[i] = lengths[j] + 1;
+                previous[i] = Some(j);
+            }
+        }
+    }
+    (lengths, previous)
+}
+
+/// Indices of the longest safe subsequence of `report`, in increasing order.
+/// Returns `None` if it has fewer than 2 levels, since such reports are not safe.
+fn longest_safe_subsequence<T>(report: &[T]) -> Option<Vec<usize>>
+where
+    T: Level,
+    LevelInt: AsPrimitive<T>,
+{
+    let (lengths, previous, end) = [increasing, decreasing]
+        .into_iter()
+        .filter_map(|adjacent| {
+            let (lengths, previous) = subsequence_lengths(report, adjacent);
+            let end = (0..lengths.len()).max_by_key(|&i| lengths[i])?;
+            Some((lengths, previous, end))
+        })
+        .max_by_key(|(lengths, _, end)| lengths[*end])?;
+    if lengths[end] < 2 {
+        return None;
+    }
+    let mut indices: Vec<_> = std::iter::successors(Some(end), |&i| previous[i]).collect();
+    indices.reverse();
+    Some(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{min_removal, min_removal_count, safe_reports_count_with_k};
+    use crate::{strict, LevelInt, Reports};
+    use generate::Seeded;
+    use rand::Rng;
+
+    /// Verify the removals of the sample reports.
+    #[test]
+    fn sample() {
+        let input = include_str!("../sample_input.txt");
+        let reports = Reports::<LevelInt>::parse(input.as_bytes()).unwrap();
+        let counts: Vec<_> = reports.iter().map(min_removal_count).collect();
+        assert_eq!(counts, [0, 2, 2, 1, 1, 0].map(Some));
+        let removals: Vec<_> = reports.iter().map(|r| min_removal(r).unwrap()).collect();
+        assert_eq!(removals[1], [0, 1]);
+        assert_eq!(removals[2], [3, 4]);
+        let counts: Vec<_> = (0..4)
+            .map(|k| safe_reports_count_with_k(&reports, k))
+            .collect();
+        assert_eq!(counts, [2, 4, 6, 6]);
+    }
+
+    /// Verify that reports without two levels that can be adjacent can't be made safe.
+    #[test]
+    fn impossible() {
+        assert_eq!(min_removal::<LevelInt>(&[]), None);
+        assert_eq!(min_removal(&[5]), None);
+        assert_eq!(min_removal(&[5, 5, 9, 20]), None);
+        assert_eq!(min_removal_count(&[5, 5, 9, 20]), None);
+        assert_eq!(min_removal(&[20, 5, 7, 40]), Some(vec![0, 3]));
+    }
+
+    /// Compare with removing every subset of levels on random reports.
+    #[test]
+    fn brute_force() {
+        let mut rng = Seeded::from_env();
+        for _ in 0..500 {
+            let len = rng.gen_range(0..=9);
+            let report: Vec<LevelInt> = (0..len).map(|_| rng.gen_range(-6..=6)).collect();
+            let expected = (0u32..1 << len)
+                .filter(|removed| {
+                    let kept: Vec<_> = (0..len)
+                        .filter(|i| removed & 1 << i == 0)
+                        .map(|i| report[i])
+                        .collect();
+                    strict::report_safety(&kept)
+                })
+                .map(|removed| removed.count_ones() as usize)
+                .min();
+            assert_eq!(min_removal_count(&report), expected, "{report:?}");
+            let removal = min_removal(&report);
+            assert_eq!(removal.as_ref().map(Vec::len), expected, "{report:?}");
+            if let Some(removal) = removal {
+                let kept: Vec<_> = (0..len)
+                    .filter(|i| !removal.contains(i))
+                    .map(|i| report[i])
+                    .collect();
+                assert!(strict::report_safety(&kept), "{report:?} {removal:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Compact storage of reports.
+//!
+//! Levels of every report are stored in a single contiguous buffer, and each report is the
+//! slice between two consecutive offsets, instead of one allocation per report.
+
+use crate::{Level, LevelInt};
+use num::cast::AsPrimitive;
+use rayon::{
+    iter::{IndexedParallelIterator, ParallelIterator as _},
+    slice::ParallelSlice as _,
+};
+use thiserror::Error;
+
+/// Reports stored in a single level buffer.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Reports<T> {
+    pub(crate) levels: Vec<T>,
+    /// Start of each report in `levels`, followed by the end of the last report.
+    pub(crate) offsets: Vec<usize>,
+}
+
+/// Error returned when parsing reports fails.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Error)]
+pub enum ParseError {
+    #[error("line {line}: unexpected byte {:?}", char::from(*byte))]
+    UnexpectedByte { line: usize, byte: u8 },
+    #[error("line {line}: level doesn't fit in the level type")]
+    OutOfRange { line: usize },
+}
+
+impl<T> Reports<T> {
+    /// Create an empty collection of reports.
+    pub fn new() -> Self {
+        Reports {
+            levels: vec![],
+            offsets: vec![0],
+        }
+    }
+
+    /// Add a report with `levels`.
+    pub fn push(&mut self, levels: &[T])
+    where
+        T: Clone,
+    {
+        self.levels.extend_from_slice(levels);
+        self.offsets.push(self.levels.len());
+    }
+
+    /// Number of reports.
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Whether there are no reports.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Levels of each report, in order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[T]> {
+        self.offsets
+            .windows(2)
+            .map(|bounds| &self.levels[bounds[0]..bounds[1]])
+    }
+
+    /// Levels of each report, in parallel.
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = &[T]>
+    where
+        T: Sync,
+    {
+        self.offsets
+            .par_windows(2)
+            .map(|bounds| &self.levels[bounds[0]..bounds[1]])
+    }
+}
+
+impl<T> Default for Reports<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, R: AsRef<[T]>> FromIterator<R> for Reports<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(reports: I) -> Self {
+        let mut collected = Self::new();
+        reports
+            .into_iter()
+            .for_each(|report| collected.push(report.as_ref()));
+        collected
+    }
+}
+
+impl<T> Reports<T>
+where
+    T: Level,
+    LevelInt: AsPrimitive<T>,
+{
+    /// Parse reports from `input`, one per line, without allocating anything but the
+    /// level and offset buffers.
+    ///
+    /// Levels are decimal integers with an optional `-` sign, separated by spaces or tabs.
+    /// Lines are split like [`str::lines`], so a final line ending is optional and empty
+    /// lines are empty reports.
+    pub fn parse(input: &[u8]) -> Result<Self, ParseError> {
+        // Every level takes at least 2 bytes, including its separator.
+        let mut reports = Reports {
+            levels: Vec::with_capacity(input.len() / 2),
+            offsets: vec![0],
+        };
+        let ten: T = 10.as_();
+        let mut line = 1;
+        let mut i = 0;
+        while i < input.len() {
+            match input[i] {
+                b' ' | b'\t' | b'\r' => i += 1,
+                b'\n' => {
+                    reports.offsets.push(reports.levels.len());
+                    line += 1;
+                    i += 1;
+                }
+                byte @ (b'-' | b'0'..=b'9') => {
+                    let negative = byte == b'-';
+                    i += usize::from(negative);
+                    let start = i;
+                    let mut level = T::zero();
+                    while let Some(digit @ b'0'..=b'9') = input.get(i) {
+                        let digit: T = LevelInt::from(digit - b'0').as_();
+                        // Accumulate negative levels below zero, so the minimum fits.
+                        level = level
+                            .checked_mul(&ten)
+                            .and_then(|level| match negative {
+                                true => level.checked_sub(&digit),
+                                false => level.checked_add(&digit),
+                            })
+                            .ok_or(ParseError::OutOfRange { line })?;
+                        i += 1;
+                    }
+                    match input.get(i) {
+                        _ if i == start => {
+                            let byte = input.get(i).copied().unwrap_or(b'\n');
+                            return Err(ParseError::UnexpectedByte { line, byte });
+                        }
+                        None | Some(b' ' | b'\t' | b'\r' | b'\n') => reports.levels.push(level),
+                        Some(&byte) => return Err(ParseError::UnexpectedByte { line, byte }),
+                    }
+                }
+                byte => return Err(ParseError::UnexpectedByte { line, byte }),
+            }
+        }
+        // The last line is a report unless the input ends with a line ending.
+        if input.last().is_some_and(|&byte| byte != b'\n') {
+            reports.offsets.push(reports.levels.len());
+        }
+        Ok(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, Reports};
+    use crate::parse_reports;
+
+    /// Verify that reports are split like lines, with signed levels.
+    #[test]
+    fn parse() {
+        let input = "7 6 4\n\n-1\t-20 3\r\n5";
+        let reports = Reports::<i32>::parse(input.as_bytes()).unwrap();
+        assert_eq!(reports, parse_reports::<i32>(input).into_iter().collect());
+        let levels: Vec<_> = reports.iter().collect();
+        assert_eq!(levels, [&[7, 6, 4][..], &[], &[-1, -20, 3], &[5]]);
+        let reports = Reports::<i32>::parse(b"1 2\n3\n").unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(Reports::<i32>::parse(b"").unwrap().is_empty());
+    }
+
+    /// Verify that invalid levels are reported with their line.
+    #[test]
+    fn parse_errors() {
+        let parse = |input: &str| Reports::<u8>::parse(input.as_bytes());
+        let unexpected = |line, byte| Err(ParseError::UnexpectedByte { line, byte });
+        assert_eq!(parse("1 2\n3 x"), unexpected(2, b'x'));
+        assert_eq!(parse("1 2a"), unexpected(1, b'a'));
+        assert_eq!(parse("1 - 2"), unexpected(1, b' '));
+        assert_eq!(parse("1\n256"), Err(ParseError::OutOfRange { line: 2 }));
+        assert_eq!(parse("-1"), Err(ParseError::OutOfRange { line: 1 }));
+        assert_eq!(parse("255 -0").unwrap().iter().next(), Some(&[255, 0][..]));
+        let min = Reports::<i8>::parse(b"-128 127").unwrap();
+        assert_eq!(min.iter().next(), Some(&[-128, 127][..]));
+    }
+}
@@ -4,15 +4,17 @@
 //! - Levels are only either increasing or decreasing.
 //! - Difference between adjacent levels is in `1..=3`.
 
-use crate::{Level, LevelInt};
-use abs_diff::AbsDiff as _;
+use crate::{Level, LevelInt, Reports};
 use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools as _;
 use num::cast::AsPrimitive;
 use std::cmp::Ordering;
 
+#[cfg(feature = "simd")]
+pub mod simd;
+
 /// Count the number of reports that are safe.
-pub(super) fn safe_reports_count<T>(reports: &[Vec<T>]) -> usize
+pub fn safe_reports_count<T>(reports: &Reports<T>) -> usize
 where
     T: Level,
     LevelInt: AsPrimitive<T>,
@@ -24,7 +26,7 @@ where
 }
 
 /// Check if a report is safe.
-pub(crate) fn report_safety<T>(report: &[T]) -> bool
+pub fn report_safety<T>(report: &[T]) -> bool
 where
     T: Level,
     LevelInt: AsPrimitive<T>,
@@ -61,32 +63,51 @@ where
 }
 
 /// Check that difference between adjacent levels is within range 1..=3.
+///
+/// Levels may be signed. A difference that doesn't fit in `T`, e.g. between levels far
+/// apart on either side of zero, is out of range.
 pub(crate) fn adjacent_safety<T>(report: impl Iterator<Item = T>) -> bool
 where
     T: Level,
     LevelInt: AsPrimitive<T>,
 {
-    report
-        .tuple_windows()
-        .all(|(a, b)| (1.as_()..=3.as_()).contains(&a.abs_diff(b)))
+    report.tuple_windows().all(|(a, b)| {
+        let (high, low) = if a > b { (a, b) } else { (b, a) };
+        high.checked_sub(&low)
+            .is_some_and(|difference| (1.as_()..=3.as_()).contains(&difference))
+    })
+}
+
+/// Whether `next` can follow `previous` in an increasing safe report.
+pub(crate) fn increasing<T>(previous: T, next: T) -> bool
+where
+    T: Level,
+    LevelInt: AsPrimitive<T>,
+{
+    next > previous
+        && next
+            .checked_sub(&previous)
+            .is_some_and(|difference| difference <= 3.as_())
+}
+
+/// Whether `next` can follow `previous` in a decreasing safe report.
+pub(crate) fn decreasing<T>(previous: T, next: T) -> bool
+where
+    T: Level,
+    LevelInt: AsPrimitive<T>,
+{
+    increasing(next, previous)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_reports, strict, tests::generate_input};
-
-    #[bench]
-    fn bench_safe_reports_count(c: &mut test::Bencher) {
-        let input = generate_input(1000);
-        let reports = crate::parse_reports(&input);
-        c.iter(|| strict::safe_reports_count(&reports));
-    }
+    use crate::{parse_reports, strict};
 
     /// Verify that input is either increasing or decreasing.
     #[test]
     fn ord() {
         let input = "1 2 3";
-        let reports = parse_reports(input);
+        let reports = parse_reports::<u32>(input);
         assert!(strict::increasing_or_decreasing(reports[0].iter().cloned()));
     }
 
@@ -94,9 +115,27 @@ mod tests {
     #[test]
     fn ord2() {
         let input = "1 2 2";
-        let reports = parse_reports(input);
+        let reports = parse_reports::<u32>(input);
         assert!(!strict::increasing_or_decreasing(
             reports[0].iter().cloned()
         ));
     }
+
+    /// Verify that reports crossing zero are checked like any other.
+    #[test]
+    fn cross_zero() {
+        let reports = parse_reports::<i32>("-2 -1 1 2\n3 1 0 -3\n1 0 -4 -5\n-1 0 0 1");
+        let safety: Vec<_> = reports.iter().map(|r| strict::report_safety(r)).collect();
+        assert_eq!(safety, [true, true, false, false]);
+    }
+
+    /// Verify that differences that don't fit in the level type are unsafe.
+    #[test]
+    fn difference_overflow() {
+        assert!(!strict::adjacent_safety([i32::MIN, i32::MAX].into_iter()));
+        assert!(!strict::adjacent_safety([i64::MAX, i64::MIN].into_iter()));
+        assert!(strict::adjacent_safety(
+            [i64::MIN, i64::MIN + 3].into_iter()
+        ));
+    }
 }
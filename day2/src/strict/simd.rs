@@ -0,0 +1,194 @@
+//! Vectorised part 1 implementation.
+//!
+//! The differences between adjacent levels of every report are computed in a single pass
+//! over the [flat level buffer](Reports), [`LANES`] levels at a time with explicit SIMD
+//! lanes. Each difference is classified by a flag byte: [`INCREASING`] if it's in `1..=3`
+//! and [`DECREASING`] if it's in `-3..=-1`. Differences between the last level of a report
+//! and the first level of the next are classified too, but never read.
+//!
+//! A report is then safe if all of its flags are increasing or all are decreasing, which is
+//! equivalent to the [scalar rules](super). The flags of a report with up to [`WIDTH`]
+//! levels fit in a single `u64`, and [`LANES`] such reports are checked at once with a mask
+//! per lane. The few longer reports are checked by the scalar version instead.
+
+use super::report_safety;
+use crate::{LevelInt, Reports};
+use std::simd::{
+    cmp::{SimdOrd as _, SimdPartialEq as _, SimdPartialOrd as _},
+    num::{SimdInt as _, SimdUint as _},
+    Mask, Select as _, Simd,
+};
+
+/// Largest number of levels of reports checked with a single mask.
+pub const WIDTH: usize = 9;
+/// Number of levels classified at once, and of reports checked at once.
+pub const LANES: usize = 8;
+/// Flag of a difference in `1..=3`.
+const INCREASING: u8 = 0b01;
+/// Flag of a difference in `-3..=-1`.
+const DECREASING: u8 = 0b10;
+/// Flags of at most `WIDTH - 1` differences, one byte each.
+type Window = u64;
+const WINDOW_BYTES: usize = Window::BITS as usize / 8;
+
+/// Count the number of reports that are safe.
+pub fn safe_reports_count(reports: &Reports<LevelInt>) -> usize {
+    let flags = difference_flags(&reports.levels);
+    let splat = Simd::splat;
+    let mut safe = 0;
+    for first in (0..reports.len()).step_by(LANES) {
+        let lanes = LANES.min(reports.len() - first);
+        // Lanes past the last report start and end at 0, so they have no differences.
+        let starts = Simd::<usize, LANES>::load_or_default(&reports.offsets[first..][..lanes]);
+        let ends = Simd::load_or_default(&reports.offsets[first + 1..][..lanes]);
+        let differences = (ends - starts).saturating_sub(splat(1));
+        // Longer reports are checked by the scalar version, and left without differences.
+        let long = differences.simd_gt(splat(WINDOW_BYTES));
+        for lane in 0..LANES {
+            if long.test(lane) {
+                let levels = &reports.levels[starts[lane]..ends[lane]];
+                safe += usize::from(report_safety(levels));
+            }
+        }
+        let differences = long.select(splat(0), differences);
+        let windows = Simd::from_array(std::array::from_fn(|lane| {
+            let window = &flags[starts[lane]..][..WINDOW_BYTES];
+            Window::from_le_bytes(window.try_into().unwrap())
+        }));
+        let safe_lanes = check_windows(windows, differences.cast());
+        safe += safe_lanes.to_bitmask().count_ones() as usize;
+    }
+    safe
+}
+
+/// Check the flags of a report in each lane, given the number of its `differences`.
+/// Lanes without differences are not safe.
+fn check_windows(
+    windows: Simd<Window, LANES>,
+    differences: Simd<Window, LANES>,
+) -> Mask<i64, LANES> {
+    let splat = Simd::splat;
+    // One in the lowest bit of the byte of each difference. The shift is kept below the
+    // width of a window in lanes without differences.
+    let unused = splat(WINDOW_BYTES as Window) - differences.simd_max(splat(1));
+    let ones = splat(Window::MAX / 0xff) >> (unused * splat(8));
+    // Shifted rather than multiplied, since the flags are single bits.
+    let increasing = ones << splat(INCREASING.trailing_zeros().into());
+    let decreasing = ones << splat(DECREASING.trailing_zeros().into());
+    differences.simd_gt(splat(0))
+        & ((windows & increasing).simd_eq(increasing) | (windows & decreasing).simd_eq(decreasing))
+}
+
+/// Flags of the difference between each level and the next.
+/// Padded with zeros so a whole [`Window`] can be read at any level.
+fn difference_flags(levels: &[LevelInt]) -> Vec<u8> {
+    // The last batch of lanes may extend past the pairs, into the padding.
+    let mut flags = vec![0; levels.len() + WINDOW_BYTES.max(LANES)];
+    let pairs = levels.len().saturating_sub(1);
+    let (previous, next) = (&levels[..pairs], &levels[levels.len() - pairs..]);
+    let mut previous = previous.chunks_exact(LANES);
+    let mut next = next.chunks_exact(LANES);
+    let mut flags_chunks = flags.chunks_exact_mut(LANES);
+    // Flags come last, so they are not advanced past the last full batch of levels.
+    for ((previous, next), flags) in (&mut previous).zip(&mut next).zip(&mut flags_chunks) {
+        classify(Simd::from_slice(previous), Simd::from_slice(next)).copy_to_slice(flags);
+    }
+    // Lanes past the last pair compare zeros, which are flagged as neither.
+    let (previous, next) = (previous.remainder(), next.remainder());
+    if !previous.is_empty() {
+        let tail = classify(Simd::load_or_default(previous), Simd::load_or_default(next));
+        flags_chunks
+            .next()
+            .expect("flags are padded by a batch")
+            .copy_from_slice(tail.as_array());
+    }
+    flags
+}
+
+/// Flag the difference between `previous` and `next` in each lane.
+fn classify(previous: Simd<LevelInt, LANES>, next: Simd<LevelInt, LANES>) -> Simd<u8, LANES> {
+    let splat = Simd::splat;
+    let difference = next - previous;
+    // The subtraction wrapped if the levels have different signs and the difference
+    // doesn't have the sign of the next level.
+    let valid = ((next ^ previous) & (next ^ difference)).simd_ge(splat(0));
+    let increasing = valid & difference.simd_ge(splat(1)) & difference.simd_le(splat(3));
+    let decreasing = valid & difference.simd_ge(splat(-3)) & difference.simd_le(splat(-1));
+    let increasing = increasing.select(splat(LevelInt::from(INCREASING)), splat(0));
+    let decreasing = decreasing.select(splat(LevelInt::from(DECREASING)), splat(0));
+    (increasing | decreasing).cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{safe_reports_count, LANES, WIDTH};
+    use crate::{strict, LevelInt, Reports};
+    use generate::Seeded;
+    use rand::Rng;
+
+    /// Compare with the scalar version on generated input.
+    #[test]
+    fn generated() {
+        let mut rng = Seeded::from_env();
+        let input = generate::day2::input(&mut rng, 1000);
+        let reports = Reports::<LevelInt>::parse(input.as_bytes()).unwrap();
+        assert_eq!(
+            safe_reports_count(&reports),
+            strict::safe_reports_count(&reports)
+        );
+    }
+
+    /// Compare with the scalar version on every number of reports up to a few batches.
+    #[test]
+    fn batches() {
+        let mut rng = Seeded::from_env();
+        let input = generate::day2::input(&mut rng, 3 * LANES);
+        let reports: Vec<_> = Reports::<LevelInt>::parse(input.as_bytes())
+            .unwrap()
+            .iter()
+            .map(<[_]>::to_vec)
+            .collect();
+        for len in 0..=reports.len() {
+            let reports: Reports<_> = reports[..len].iter().collect();
+            assert_eq!(
+                safe_reports_count(&reports),
+                strict::safe_reports_count(&reports),
+                "{len} reports"
+            );
+        }
+    }
+
+    /// Compare with the scalar version on reports of every width, with small steps and
+    /// extreme levels.
+    #[test]
+    fn differential() {
+        let mut rng = Seeded::from_env();
+        let mut reports = Reports::new();
+        for _ in 0..2000 {
+            let width = rng.gen_range(0..=WIDTH + 2);
+            let mut level: LevelInt = match rng.gen_range(0..4) {
+                0 => LevelInt::MIN,
+                1 => LevelInt::MAX,
+                _ => rng.gen_range(-20..20),
+            };
+            let direction = if rng.gen() { 1 } else { -1 };
+            let report: Vec<_> = (0..width)
+                .map(|_| {
+                    let step = match rng.gen_range(0..10) {
+                        0 => rng.gen_range(-5..=5),
+                        1 => LevelInt::MAX,
+                        _ => direction * rng.gen_range(1..=3),
+                    };
+                    let old_level = level;
+                    level = level.wrapping_add(step);
+                    old_level
+                })
+                .collect();
+            reports.push(&report);
+        }
+        let expected = strict::safe_reports_count(&reports);
+        assert!(expected > 0);
+        assert_eq!(safe_reports_count(&reports), expected);
+        assert_eq!(safe_reports_count(&Reports::new()), 0);
+    }
+}
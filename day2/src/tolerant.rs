@@ -6,12 +6,12 @@
 //! - The report would otherwise be safe according to the previous rules
 //!   as long as at most one of the levels is removed from the report.
 
-use crate::{strict, Level, LevelInt};
+use crate::{strict, Level, LevelInt, Reports};
 use num::cast::AsPrimitive;
-use rayon::iter::{IntoParallelRefIterator as _, ParallelIterator as _};
+use rayon::iter::ParallelIterator as _;
 
 /// Count the number of reports that are safe.
-pub(super) fn safe_reports_count<T>(reports: &[Vec<T>]) -> usize
+pub fn safe_reports_count<T>(reports: &Reports<T>) -> usize
 where
     T: Level + Sync,
     LevelInt: AsPrimitive<T>,
@@ -26,7 +26,7 @@ where
 /// Check if a report is safe.
 /// If safety fails using strict rules, try skipping one level from the report and check again,
 /// until all attempts fail.
-fn report_safety<T>(report: &[T]) -> bool
+pub fn report_safety<T>(report: &[T]) -> bool
 where
     T: Level,
     LevelInt: AsPrimitive<T>,
@@ -52,15 +52,3 @@ fn skip_nth_level<T>(report: impl Iterator<Item = T>, n: usize) -> impl Iterator
         .enumerate()
         .filter_map(move |(index, level)| if index == n { None } else { Some(level) })
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::{parse_reports, tests::generate_input, tolerant};
-
-    #[bench]
-    fn bench_safe_reports_count(c: &mut test::Bencher) {
-        let input = generate_input(1000);
-        let reports = parse_reports(&input);
-        c.iter(|| tolerant::safe_reports_count(&reports));
-    }
-}
@@ -0,0 +1,43 @@
+//! Tests of the public API of day 2.
+
+use day2::{parse_reports, strict, tolerant, Day2, Reports};
+use solution::Solution;
+
+const SAMPLE: &str = include_str!("../sample_input.txt");
+
+/// Verify the safety of each sample report under both rules.
+#[test]
+fn report_safety() {
+    let reports = parse_reports::<u32>(SAMPLE);
+    let strict: Vec<_> = reports.iter().map(|r| strict::report_safety(r)).collect();
+    let tolerant: Vec<_> = reports.iter().map(|r| tolerant::report_safety(r)).collect();
+    assert_eq!(strict, [true, false, false, false, false, true]);
+    assert_eq!(tolerant, [true, false, false, true, true, true]);
+}
+
+/// Verify that both layouts hold the same reports, and that the solution matches counting
+/// them with the library functions.
+#[test]
+fn solution() {
+    let reports = Reports::<u32>::parse(SAMPLE.as_bytes()).unwrap();
+    assert_eq!(reports, parse_reports::<u32>(SAMPLE).into_iter().collect());
+    let input = Day2::parse(SAMPLE.as_bytes());
+    assert_eq!(
+        Day2::part1(&input),
+        strict::safe_reports_count(&reports).to_string()
+    );
+    assert_eq!(
+        Day2::part2(&input),
+        tolerant::safe_reports_count(&reports).to_string()
+    );
+}
+
+/// Verify the safety of reports with negative levels, some of them crossing zero.
+#[test]
+fn negative_levels() {
+    let reports = parse_reports::<i32>(include_str!("../sample_input_negative.txt"));
+    let strict: Vec<_> = reports.iter().map(|r| strict::report_safety(r)).collect();
+    let tolerant: Vec<_> = reports.iter().map(|r| tolerant::report_safety(r)).collect();
+    assert_eq!(strict, [true, true, false, false, false]);
+    assert_eq!(tolerant, [true, true, true, true, false]);
+}
@@ -0,0 +1,10 @@
+//! Benchmarks of day 3 on generated input.
+
+use day3::Day3;
+use generate::{Seeded, DEFAULT_SEED};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let generate = |size| generate::day3::input(&mut Seeded::from_env_or(DEFAULT_SEED), size);
+    bench::main::<Day3>(generate, generate::day3::DEFAULT_SIZE)
+}
@@ -0,0 +1,63 @@
+//! Instruction machine that retains its state between executed instructions.
+
+use crate::{ExecutionError, Operation, Overflow};
+use num::{CheckedAdd, CheckedMul, Zero};
+use std::fmt::Debug;
+
+/// Retained state of the program formed by a sequence of [`Operation`]s.
+/// Instructions are executed one at a time with [`Interpreter::step`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Interpreter<T> {
+    /// Whether `Mul` operations are currently enabled.
+    pub enabled: bool,
+    /// Sum of the results of the executed operations.
+    pub sum: T,
+    /// Number of operations executed so far.
+    pub executed: usize,
+}
+
+impl<T> Interpreter<T>
+where
+    T: Zero,
+{
+    /// Create an interpreter in the initial state.
+    pub fn new() -> Self {
+        Interpreter {
+            enabled: true,
+            sum: T::zero(),
+            executed: 0,
+        }
+    }
+}
+
+impl<T> Default for Interpreter<T>
+where
+    T: Zero,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Interpreter<T>
+where
+    T: CheckedMul + CheckedAdd + Clone + Debug,
+{
+    /// Execute the next operation and update the state.
+    /// The state is left unchanged if the operation overflows.
+    pub fn step(&mut self, operation: &Operation<T>) -> Result<(), ExecutionError<T>> {
+        let error = |overflow| ExecutionError {
+            index: self.executed,
+            operation: operation.clone(),
+            overflow,
+        };
+        if let Some(product) = operation.execute(&mut self.enabled).map_err(error)? {
+            self.sum = self
+                .sum
+                .checked_add(&product)
+                .ok_or_else(|| error(Overflow::Sum))?;
+        }
+        self.executed += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+//! Day 3: Mull It Over.
+//!
+//! The input is corrupted memory containing `mul`, `do` and `don't` instructions.
+//! The [`parser`] finds the instructions, which are then [executed](execute) with the
+//! integer type `T`. Part 1 executes only `mul` instructions and part 2 executes all of
+//! them, which [`solve`] does at once. [`Day3`] wraps both in the [`Solution`] interface
+//! with `u32` integers.
+
+use num::{CheckedAdd, CheckedMul, Zero};
+use solution::Solution;
+use std::{fmt::Debug, io::BufRead, str::FromStr};
+use thiserror::Error;
+
+pub mod interpreter;
+pub mod parser;
+pub mod repl;
+
+use interpreter::Interpreter;
+use parser::Grammar;
+
+/// Solution for day 3.
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    /// Every operation found in the input.
+    type Input = Vec<Operation<u32>>;
+
+    fn parse(input: &[u8]) -> Self::Input {
+        parser::with_ops_parser(input, |i| parser::any_op(Grammar::default(), i))
+    }
+
+    fn part1(instructions: &Self::Input) -> String {
+        // Every `mul` found by `any_op` is also found by `mul_op`.
+        let instructions: Vec<_> = instructions
+            .iter()
+            .filter(|operation| matches!(operation, Operation::Mul { .. }))
+            .cloned()
+            .collect();
+        execute(&instructions)
+            .expect("execution must not overflow")
+            .to_string()
+    }
+
+    fn part2(instructions: &Self::Input) -> String {
+        execute(instructions)
+            .expect("execution must not overflow")
+            .to_string()
+    }
+}
+
+/// Integer type used to execute instructions.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Backend {
+    #[default]
+    U32,
+    U64,
+    U128,
+    /// Signed integers. Required to execute signed operands.
+    I64,
+    /// Arbitrary-precision integers. Execution cannot overflow.
+    BigInt,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "u32" => Ok(Backend::U32),
+            "u64" => Ok(Backend::U64),
+            "u128" => Ok(Backend::U128),
+            "i64" => Ok(Backend::I64),
+            "bigint" => Ok(Backend::BigInt),
+            _ => Err(format!(
+                "unknown backend `{s}` (expected `u32`, `u64`, `u128`, `i64` or `bigint`)"
+            )),
+        }
+    }
+}
+
+/// Result of executing a sequence of instructions.
+pub type ExecutionResult<T> = Result<T, ExecutionError<T>>;
+
+/// Parse and execute instructions in `input`.
+/// Returns the results of executing only `mul` operations and all operations.
+pub fn solve<T>(input: &[u8], grammar: Grammar) -> (ExecutionResult<T>, ExecutionResult<T>)
+where
+    T: FromStr + CheckedMul + CheckedAdd + Zero + Clone + Debug,
+    <T as FromStr>::Err: Debug,
+{
+    let mul_only = parser::with_ops_parser(input, move |i| parser::mul_op::<T, _>(grammar, i));
+    let all = parser::with_ops_parser(input, move |i| parser::any_op::<T, _>(grammar, i));
+    (execute(&mul_only), execute(&all))
+}
+
+/// Parse and execute instructions read from `reader` in a single pass.
+/// Returns the results of executing only `mul` operations and all operations.
+pub fn execute_stream<T>(
+    reader: impl BufRead,
+    grammar: Grammar,
+) -> std::io::Result<(ExecutionResult<T>, ExecutionResult<T>)>
+where
+    T: FromStr + CheckedMul + CheckedAdd + Zero + Clone + Debug,
+{
+    let mut mul_only = (Interpreter::new(), Ok(()));
+    let mut all = (Interpreter::new(), Ok(()));
+    let ops =
+        parser::StreamParser::new(reader, grammar, move |i| parser::any_op::<T, _>(grammar, i));
+    for operation in ops {
+        let operation = operation?;
+        // Stop executing on an interpreter once it fails.
+        if let Operation::Mul { .. } = operation {
+            mul_only.1 = mul_only.1.and_then(|_| mul_only.0.step(&operation));
+        }
+        all.1 = all.1.and_then(|_| all.0.step(&operation));
+    }
+    let finish =
+        |(interpreter, result): (Interpreter<T>, Result<(), _>)| result.map(|_| interpreter.sum);
+    Ok((finish(mul_only), finish(all)))
+}
+
+/// Instruction to execute.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operation<T> {
+    Mul { left: T, right: T },
+    Do,
+    Dont,
+}
+
+/// Arithmetic step that overflowed during execution.
+#[derive(Debug, PartialEq, Clone, Copy, Error)]
+pub enum Overflow {
+    #[error("product overflowed")]
+    Product,
+    #[error("sum overflowed")]
+    Sum,
+}
+
+/// Error returned when executing a sequence of instructions fails.
+#[derive(Debug, PartialEq, Clone, Error)]
+#[error("instruction #{index} `{operation:?}`: {overflow}")]
+pub struct ExecutionError<T: Debug> {
+    /// Index of the instruction that caused the error.
+    pub index: usize,
+    /// The instruction that caused the error.
+    pub operation: Operation<T>,
+    pub overflow: Overflow,
+}
+
+impl<T> Operation<T>
+where
+    T: CheckedMul,
+{
+    /// Execute the operation. This may affect the state provided to the function.
+    /// Returns the result of the operation if applicable.
+    pub fn execute(&self, enabled_state: &mut bool) -> Result<Option<T>, Overflow> {
+        match self {
+            Operation::Mul { left, right } => enabled_state
+                .then(|| left.checked_mul(right).ok_or(Overflow::Product))
+                .transpose(),
+            Operation::Do => {
+                *enabled_state = true;
+                Ok(None)
+            }
+            Operation::Dont => {
+                *enabled_state = false;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Execute a sequence of instructions.
+/// Returns the result of the execution, or the first instruction that overflowed.
+pub fn execute<T>(instructions: &[Operation<T>]) -> Result<T, ExecutionError<T>>
+where
+    T: CheckedMul + CheckedAdd + Zero + Clone + Debug,
+{
+    // This is the retained state of the program that is formed
+    // by our instruction list.
+    let mut interpreter = Interpreter::new();
+    // This has to be executed sequentially!
+    // `Do`/`Dont` ops can change state, which affect `Mul` ops.
+    instructions
+        .iter()
+        .try_for_each(|operation| interpreter.step(operation))?;
+    Ok(interpreter.sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execute, execute_stream, parser, Day3, ExecutionError, Grammar, Operation, Overflow,
+    };
+    use generate::Seeded;
+    use num::BigInt;
+    use solution::Solution;
+
+    solution::sample_tests!(crate::Day3);
+
+    /// Verify that a product exceeding `u16` is reported with its instruction.
+    #[test]
+    fn overflow_product() {
+        let input = b"mul(2,3)don't()mul(999,999)do()mul(999,999)";
+        let instructions =
+            parser::with_ops_parser::<u16, _>(input, |i| parser::any_op(Grammar::default(), i));
+        assert_eq!(
+            execute(&instructions),
+            Err(ExecutionError {
+                index: 4,
+                operation: Operation::Mul {
+                    left: 999,
+                    right: 999
+                },
+                overflow: Overflow::Product,
+            })
+        );
+    }
+
+    /// Verify that a sum exceeding `u16` is reported with its instruction.
+    #[test]
+    fn overflow_sum() {
+        let input = b"mul(255,255)mul(255,255)mul(1,1)";
+        let instructions =
+            parser::with_ops_parser::<u16, _>(input, |i| parser::mul_op(Grammar::default(), i));
+        assert_eq!(
+            execute(&instructions).map_err(|e| (e.index, e.overflow)),
+            Err((1, Overflow::Sum))
+        );
+    }
+
+    /// Verify that the [`BigInt`] backend computes results exceeding `u64` exactly.
+    #[test]
+    fn bigint_exceeds_u64() {
+        let input = b"mul(9999999999,9999999999)mul(18446744073709551615,18446744073709551615)";
+        let grammar = Grammar {
+            max_digits: 20,
+            ..Default::default()
+        };
+        let instructions =
+            parser::with_ops_parser::<BigInt, _>(input, |i| parser::mul_op(grammar, i));
+        let expected = BigInt::from(9999999999u64) * BigInt::from(9999999999u64)
+            + BigInt::from(u64::MAX) * BigInt::from(u64::MAX);
+        assert!(expected > BigInt::from(u64::MAX));
+        assert_eq!(execute(&instructions).unwrap(), expected);
+    }
+
+    /// Verify that streaming execution computes both parts in a single pass.
+    #[test]
+    fn stream() {
+        let input = include_bytes!("../sample_input_any.txt");
+        let reader = std::io::BufReader::with_capacity(4, &input[..]);
+        let (mul_only, all) = execute_stream::<u32>(reader, Grammar::default()).unwrap();
+        assert_eq!(mul_only, Ok(161));
+        assert_eq!(all, Ok(48));
+    }
+
+    /// Verify that streaming execution matches execution of the parsed input on generated input.
+    #[test]
+    fn stream_generated() {
+        let mut rng = Seeded::from_env();
+        let input = generate::day3::input(&mut rng, 1000);
+        let reader = std::io::BufReader::with_capacity(64, input.as_bytes());
+        let (mul_only, all) = execute_stream::<u32>(reader, Grammar::default()).unwrap();
+        let instructions = Day3::parse(input.as_bytes());
+        assert_eq!(mul_only.unwrap().to_string(), Day3::part1(&instructions));
+        assert_eq!(all.unwrap().to_string(), Day3::part2(&instructions));
+    }
+
+    /// Test both parts on generated input with planted instructions and near misses.
+    #[test]
+    fn planted() {
+        let mut rng = Seeded::from_env();
+        let planted = generate::day3::planted(&mut rng, 2000);
+        let instructions = Day3::parse(planted.input.as_bytes());
+        assert_eq!(Day3::part1(&instructions), planted.part1.to_string());
+        assert_eq!(Day3::part2(&instructions), planted.part2.to_string());
+    }
+}
@@ -1,90 +1,166 @@
-#![feature(test)]
-#![feature(iter_array_chunks)]
+use day3::{execute_stream, parser, parser::Grammar, repl::Repl, Backend};
+use num::{BigInt, CheckedAdd, CheckedMul, Zero};
+use std::{
+    fmt::{Debug, Display},
+    io::BufRead,
+    str::FromStr,
+};
 
-use std::{iter::Sum, ops::Mul};
+/// Command line arguments.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+struct Args {
+    /// Selected with `--backend <name>`.
+    backend: Backend,
+    /// Variations enabled with `--whitespace`, `--signed` and `--ignore-case`, and the
+    /// operand digit limit set with `--max-digits <n>`.
+    grammar: Grammar,
+    /// Enabled with `--near-misses`.
+    near_misses: bool,
+    /// Enabled with `--stream`.
+    stream: bool,
+    /// Enabled with the `repl` subcommand.
+    repl: bool,
+}
 
-mod parser;
+impl Args {
+    const USAGE: &str = "usage: day3 [repl] [--backend <u32|u64|u128|i64|bigint>] \
+        [--whitespace] [--signed] [--ignore-case] [--max-digits <n>] \
+        [--near-misses | --stream]";
 
-// Benchmarking utility.
-extern crate test;
+    /// Read arguments from the command line.
+    fn from_env() -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--backend" => {
+                    parsed.backend = args
+                        .next()
+                        .ok_or_else(|| "missing value for `--backend`".to_owned())?
+                        .parse()?
+                }
+                "--whitespace" => parsed.grammar.whitespace = true,
+                "--signed" => parsed.grammar.signed = true,
+                "--ignore-case" => parsed.grammar.case_insensitive = true,
+                "--max-digits" => {
+                    parsed.grammar.max_digits = args
+                        .next()
+                        .ok_or_else(|| "missing value for `--max-digits`".to_owned())?
+                        .parse()
+                        .ok()
+                        .filter(|&digits| digits > 0)
+                        .ok_or_else(|| "`--max-digits` must be a positive integer".to_owned())?
+                }
+                "--near-misses" => parsed.near_misses = true,
+                "--stream" => parsed.stream = true,
+                "repl" => parsed.repl = true,
+                _ => return Err(format!("unexpected argument `{arg}`")),
+            }
+        }
+        if parsed.near_misses && parsed.stream {
+            return Err("`--near-misses` cannot be combined with `--stream`".to_owned());
+        }
+        if parsed.repl && (parsed.near_misses || parsed.stream) {
+            return Err("`repl` cannot be combined with `--near-misses` or `--stream`".to_owned());
+        }
+        Ok(parsed)
+    }
+}
 
 /// Solve both parts and print results to standard output.
 fn main() {
-    let input = input::stdin_file();
-    let instructions = parser::with_ops_parser(&input, parser::mul_op);
-    let output: u32 = execute(&instructions);
-    println!("execution result (only `mul`): {output}");
-    let instructions = parser::with_ops_parser(&input, parser::any_op);
-    let output: u32 = execute(&instructions);
-    println!("execution result (all operations): {output}");
+    let args = match Args::from_env() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{}", Args::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.repl {
+        return match args.backend {
+            Backend::U32 => repl::<u32>(args.grammar),
+            Backend::U64 => repl::<u64>(args.grammar),
+            Backend::U128 => repl::<u128>(args.grammar),
+            Backend::I64 => repl::<i64>(args.grammar),
+            Backend::BigInt => repl::<BigInt>(args.grammar),
+        };
+    }
+    if args.stream {
+        let reader = input::stdin_file_reader(STREAM_CHUNK_SIZE);
+        return match args.backend {
+            Backend::U32 => solve_stream::<u32>(reader, args.grammar),
+            Backend::U64 => solve_stream::<u64>(reader, args.grammar),
+            Backend::U128 => solve_stream::<u128>(reader, args.grammar),
+            Backend::I64 => solve_stream::<i64>(reader, args.grammar),
+            Backend::BigInt => solve_stream::<BigInt>(reader, args.grammar),
+        };
+    }
+    let input = input::stdin_file_bytes();
+    match args.backend {
+        Backend::U32 => solve::<u32>(&input, args),
+        Backend::U64 => solve::<u64>(&input, args),
+        Backend::U128 => solve::<u128>(&input, args),
+        Backend::I64 => solve::<i64>(&input, args),
+        Backend::BigInt => solve::<BigInt>(&input, args),
+    }
 }
 
-/// Instruction to execute.
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Operation<T> {
-    Mul { left: T, right: T },
-    Do,
-    Dont,
-}
+/// Size of the chunks read from the input file with `--stream`.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
 
-impl<T> Operation<T>
+/// Start an interactive session on standard input and output.
+fn repl<T>(grammar: Grammar)
 where
-    for<'a> &'a T: Mul<&'a T, Output = T>,
+    T: FromStr + CheckedMul + CheckedAdd + Zero + Clone + Display + Debug,
 {
-    /// Execute the operation. This may affect the state provided to the function.
-    /// Returns the result of the operation if applicable.
-    fn execute(&self, enabled_state: &mut bool) -> Option<T> {
-        match self {
-            Operation::Mul { left, right } => enabled_state.then(|| left * right),
-            Operation::Do => {
-                *enabled_state = true;
-                None
-            }
-            Operation::Dont => {
-                *enabled_state = false;
-                None
-            }
-        }
+    let stdin = std::io::stdin().lock();
+    if let Err(e) = Repl::<T>::new(grammar).run(stdin, std::io::stdout()) {
+        eprintln!("repl io error: {e}");
     }
 }
 
-/// Execute a sequence of instructions.
-/// Returns the result of the execution.
-fn execute<T>(instructions: &[Operation<T>]) -> T
+/// Solve both parts using `T` to execute instructions and print results to standard output.
+/// Prints the near miss report after the results if requested.
+fn solve<T>(input: &[u8], args: Args)
 where
-    for<'a> &'a T: Mul<&'a T, Output = T>,
-    T: Sum,
+    T: FromStr + CheckedMul + CheckedAdd + Zero + Clone + Display + Debug,
+    <T as FromStr>::Err: Debug,
 {
-    // This is the retained state of the program that is formed
-    // by our instruction list.
-    let mut enabled = true;
-    instructions
-        // This has to be executed sequentially!
-        // `Do`/`Dont` ops can change state, which affect `Mul` ops.
-        .iter()
-        // Fortunately, a `&mut` stops us from mistakenly sharing state
-        // between threads anyway. :)
-        .filter_map(|i| i.execute(&mut enabled))
-        .sum()
+    let grammar = args.grammar;
+    let (mul_only, all) = day3::solve::<T>(input, grammar);
+    match mul_only {
+        Ok(output) => println!("execution result (only `mul`): {output}"),
+        Err(e) => eprintln!("execution failed (only `mul`): {e}"),
+    }
+    match all {
+        Ok(output) => println!("execution result (all operations): {output}"),
+        Err(e) => eprintln!("execution failed (all operations): {e}"),
+    }
+    if args.near_misses {
+        let misses = parser::near_misses::<T>(input, grammar);
+        println!("near misses ({grammar}): {}", misses.len());
+        misses.iter().for_each(|miss| println!("{miss}"));
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{execute, parser};
-
-    /// Test part 1 on sample input.
-    #[test]
-    fn part1() {
-        let instructions =
-            parser::with_ops_parser(include_str!("../sample_input_mul.txt"), parser::mul_op);
-        assert_eq!(161u32, execute(&instructions));
+/// Solve both parts in a single pass over `reader` and print results to standard output.
+/// Only the current chunk of input is kept in memory.
+fn solve_stream<T>(reader: impl BufRead, grammar: Grammar)
+where
+    T: FromStr + CheckedMul + CheckedAdd + Zero + Clone + Display + Debug,
+    <T as FromStr>::Err: Debug,
+{
+    let (mul_only, all) = match execute_stream::<T>(reader, grammar) {
+        Ok(results) => results,
+        Err(e) => return eprintln!("file read io error: {e}"),
+    };
+    match mul_only {
+        Ok(output) => println!("execution result (only `mul`): {output}"),
+        Err(e) => eprintln!("execution failed (only `mul`): {e}"),
     }
-
-    /// Test part 2 on sample input.
-    #[test]
-    fn part2() {
-        let instructions =
-            parser::with_ops_parser(include_str!("../sample_input_any.txt"), parser::any_op);
-        assert_eq!(48u32, execute(&instructions));
+    match all {
+        Ok(output) => println!("execution result (all operations): {output}"),
+        Err(e) => eprintln!("execution failed (all operations): {e}"),
     }
 }
@@ -1,77 +1,241 @@
-use std::fmt::Debug;
+//! Instruction parsers.
+//!
+//! Parsers are built from streaming primitives - a parser that runs out of input before
+//! an instruction is complete returns [`nom::Err::Incomplete`]. When the whole input
+//! is available, this is treated the same as a parsing failure.
+
+use std::fmt::{Debug, Display};
+use std::io::{self, BufRead};
 use std::str::FromStr;
 
 use crate::Operation;
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{char, one_of},
-    combinator::{peek, recognize},
-    multi::many1,
+    bytes::streaming::{tag, tag_no_case},
+    character::streaming::{char, one_of},
+    combinator::{cond, opt, peek, recognize},
+    error::{context, ContextError, ErrorKind, ParseError, VerboseError, VerboseErrorKind},
+    multi::many_m_n,
+    sequence::pair,
     IResult, Parser as _,
 };
 
-/// Parse an integer of type `T`.
-fn integer<T>(input: &str) -> IResult<&str, T>
+/// Default maximum number of digits of an operand, as in the puzzle.
+pub const DEFAULT_MAX_DIGITS: usize = 3;
+/// Default maximum number of whitespace characters between two tokens.
+pub const DEFAULT_MAX_WHITESPACE: usize = 16;
+
+/// Syntax variations accepted in addition to the exact instruction syntax.
+/// The default grammar only accepts the exact syntax, e.g. `mul(1,2)`, with operands of up
+/// to [`DEFAULT_MAX_DIGITS`] digits.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Grammar {
+    /// Allow up to `max_whitespace` whitespace characters between tokens inside
+    /// parentheses, e.g. `mul( 1 , 2 )`.
+    pub whitespace: bool,
+    /// Allow operands prefixed with a sign, e.g. `mul(-1,+2)`.
+    pub signed: bool,
+    /// Match keywords regardless of case, e.g. `MUL(1,2)` or `Don't()`.
+    pub case_insensitive: bool,
+    /// Maximum number of digits of an operand. Operands with more digits don't parse.
+    pub max_digits: usize,
+    /// Maximum number of whitespace characters between two tokens, if whitespace is
+    /// allowed. Bounds the lookahead of the [`StreamParser`].
+    pub max_whitespace: usize,
+}
+
+impl Default for Grammar {
+    fn default() -> Self {
+        Grammar {
+            whitespace: false,
+            signed: false,
+            case_insensitive: false,
+            max_digits: DEFAULT_MAX_DIGITS,
+            max_whitespace: DEFAULT_MAX_WHITESPACE,
+        }
+    }
+}
+
+impl Grammar {
+    /// Grammar that accepts every supported syntax variation, with the default limits.
+    pub const LENIENT: Grammar = Grammar {
+        whitespace: true,
+        signed: true,
+        case_insensitive: true,
+        max_digits: DEFAULT_MAX_DIGITS,
+        max_whitespace: DEFAULT_MAX_WHITESPACE,
+    };
+
+    /// Length of the longest instruction: `mul` with signed operands of `max_digits`
+    /// digits, and `max_whitespace` whitespace characters around both operands.
+    pub fn max_instruction_len(&self) -> usize {
+        let operands = self.max_digits.saturating_add(1).saturating_mul(2);
+        let whitespace = self.max_whitespace.saturating_mul(4);
+        "mul(,)"
+            .len()
+            .saturating_add(operands)
+            .saturating_add(whitespace)
+    }
+}
+
+impl Grammar {
+    /// Combine the syntax variations allowed by both grammars.
+    /// The limits of `self` are kept.
+    fn union(self, other: Grammar) -> Grammar {
+        Grammar {
+            whitespace: self.whitespace || other.whitespace,
+            signed: self.signed || other.signed,
+            case_insensitive: self.case_insensitive || other.case_insensitive,
+            ..self
+        }
+    }
+}
+
+impl Display for Grammar {
+    /// List the enabled syntax variations.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let options = [
+            (self.whitespace, "whitespace"),
+            (self.signed, "signed operands"),
+            (self.case_insensitive, "case-insensitive keywords"),
+        ];
+        let enabled: Vec<_> = options
+            .into_iter()
+            .filter_map(|(enabled, name)| enabled.then_some(name))
+            .collect();
+        match enabled.is_empty() {
+            true => write!(f, "exact syntax"),
+            false => write!(f, "{}", enabled.join(", ")),
+        }
+    }
+}
+
+/// Parse a keyword, ignoring case if allowed by the grammar.
+fn keyword<'a, E>(grammar: Grammar, keyword: &'static str, i: &'a [u8]) -> IResult<&'a [u8], (), E>
+where
+    E: ParseError<&'a [u8]>,
+{
+    let (i, _) = match grammar.case_insensitive {
+        true => tag_no_case(keyword)(i)?,
+        false => tag(keyword)(i)?,
+    };
+    Ok((i, ()))
+}
+
+/// Skip up to [`Grammar::max_whitespace`] whitespace characters if allowed by the grammar.
+fn space<'a, E>(grammar: Grammar, i: &'a [u8]) -> IResult<&'a [u8], (), E>
+where
+    E: ParseError<&'a [u8]>,
+{
+    let whitespace = many_m_n(0, grammar.max_whitespace, one_of(" \t\r\n"));
+    let (i, _) = cond(grammar.whitespace, whitespace)(i)?;
+    Ok((i, ()))
+}
+
+/// Parse an integer of type `T` with 1 to [`Grammar::max_digits`] digits.
+/// A leading sign is only accepted if allowed by the grammar.
+fn integer<'a, T, E>(grammar: Grammar, input: &'a [u8]) -> IResult<&'a [u8], T, E>
 where
-    <T as FromStr>::Err: Debug,
     T: FromStr,
+    E: ParseError<&'a [u8]> + ContextError<&'a [u8]>,
 {
-    recognize(many1(one_of("0123456789")))
-        .parse(input)
-        .map(|(i, s)| (i, s.parse().expect("decimal")))
+    let (i, s) = recognize(pair(
+        cond(grammar.signed, opt(one_of("+-"))),
+        many_m_n(1, grammar.max_digits, one_of("0123456789")),
+    ))
+    .parse(input)?;
+    let s = std::str::from_utf8(s).expect("decimal digits are ascii");
+    match s.parse() {
+        Ok(integer) => Ok((i, integer)),
+        // Operand is not representable by `T`.
+        Err(_) => {
+            let error = E::from_error_kind(input, ErrorKind::MapRes);
+            Err(nom::Err::Error(E::add_context(
+                input,
+                "operand in range",
+                error,
+            )))
+        }
+    }
 }
 
-/// Parse [`Operation::Mul`].
-pub(super) fn mul_op<T>(i: &str) -> IResult<&str, Operation<T>>
+/// Parse [`Operation::Mul`] according to the provided [`Grammar`].
+pub fn mul_op<'a, T, E>(grammar: Grammar, i: &'a [u8]) -> IResult<&'a [u8], Operation<T>, E>
 where
-    <T as FromStr>::Err: Debug,
-    T: FromStr + Copy,
+    T: FromStr,
+    E: ParseError<&'a [u8]> + ContextError<&'a [u8]>,
 {
-    let (i, _) = tag("mul")(i)?;
-    let (i, _) = tag("(")(i)?;
-    let (i, left) = integer(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, right) = integer(i)?;
-    let (i, _) = tag(")")(i)?;
+    let (i, _) = keyword(grammar, "mul", i)?;
+    let (i, _) = context("`(`", tag("("))(i)?;
+    let (i, _) = space(grammar, i)?;
+    let (i, left) = context("operand", |i| integer(grammar, i))(i)?;
+    let (i, _) = space(grammar, i)?;
+    let (i, _) = context("`,`", char(','))(i)?;
+    let (i, _) = space(grammar, i)?;
+    let (i, right) = context("operand", |i| integer(grammar, i))(i)?;
+    let (i, _) = space(grammar, i)?;
+    let (i, _) = context("`)`", tag(")"))(i)?;
     Ok((i, Operation::Mul { left, right }))
 }
 
-/// Parse [`Operation::Do`].
-fn do_op<T>(i: &str) -> IResult<&str, Operation<T>> {
-    let (i, _) = tag("do()")(i)?;
+/// Parse an empty argument list, `()`.
+fn empty_args<'a, E>(grammar: Grammar, i: &'a [u8]) -> IResult<&'a [u8], (), E>
+where
+    E: ParseError<&'a [u8]> + ContextError<&'a [u8]>,
+{
+    let (i, _) = context("`(`", tag("("))(i)?;
+    let (i, _) = space(grammar, i)?;
+    let (i, _) = context("`)`", tag(")"))(i)?;
+    Ok((i, ()))
+}
+
+/// Parse [`Operation::Do`] according to the provided [`Grammar`].
+fn do_op<'a, T, E>(grammar: Grammar, i: &'a [u8]) -> IResult<&'a [u8], Operation<T>, E>
+where
+    E: ParseError<&'a [u8]> + ContextError<&'a [u8]>,
+{
+    let (i, _) = keyword(grammar, "do", i)?;
+    let (i, _) = empty_args(grammar, i)?;
     Ok((i, Operation::Do))
 }
 
-/// Parse [`Operation::Dont`].
-fn dont_op<T>(i: &str) -> IResult<&str, Operation<T>> {
-    let (i, _) = tag("don't()")(i)?;
+/// Parse [`Operation::Dont`] according to the provided [`Grammar`].
+fn dont_op<'a, T, E>(grammar: Grammar, i: &'a [u8]) -> IResult<&'a [u8], Operation<T>, E>
+where
+    E: ParseError<&'a [u8]> + ContextError<&'a [u8]>,
+{
+    let (i, _) = keyword(grammar, "don't", i)?;
+    let (i, _) = empty_args(grammar, i)?;
     Ok((i, Operation::Dont))
 }
 
-/// Parse any [`Operation`].
-pub(super) fn any_op<T>(i: &str) -> IResult<&str, Operation<T>>
+/// Parse any [`Operation`] according to the provided [`Grammar`].
+pub fn any_op<'a, T, E>(grammar: Grammar, i: &'a [u8]) -> IResult<&'a [u8], Operation<T>, E>
 where
-    <T as FromStr>::Err: Debug,
-    T: Copy + FromStr,
+    T: FromStr,
+    E: ParseError<&'a [u8]> + ContextError<&'a [u8]>,
 {
-    alt((mul_op::<T>, do_op, dont_op))(i)
+    alt((
+        |i| mul_op(grammar, i),
+        |i| do_op(grammar, i),
+        |i| dont_op(grammar, i),
+    ))(i)
 }
 
-/// Skip one character in the provided string and return the remainder.
-/// Returns `None` if the string is empty.
-fn skip_one(i: &str) -> Option<&str> {
-    i.split_at_checked(1).map(|(_, i)| i)
+/// Skip one byte in the provided input and return the remainder.
+/// Returns `None` if the input is empty.
+fn skip_one(i: &[u8]) -> Option<&[u8]> {
+    i.split_first().map(|(_, i)| i)
 }
 
-/// Parse a string into a list of [`Operation`]s.
+/// Parse raw bytes into a list of [`Operation`]s.
+/// The input does not have to be valid UTF-8 - bytes that do not form an [`Operation`]
+/// are skipped.
 /// The provided `ops_parser` is used to peek forward and attempt to parse an [`Operation`].
 /// This allows limiting the amount of supported operations.
-pub(super) fn with_ops_parser<T, F>(mut input: &str, ops_parser: F) -> Vec<Operation<T>>
+pub fn with_ops_parser<T, F>(mut input: &[u8], ops_parser: F) -> Vec<Operation<T>>
 where
-    <T as FromStr>::Err: Debug,
-    T: Copy + FromStr + Debug,
-    F: Fn(&str) -> IResult<&str, Operation<T>> + Copy,
+    F: Fn(&[u8]) -> IResult<&[u8], Operation<T>> + Copy,
 {
     let mut ops = Vec::new();
     // Peek forward and parse operations.
@@ -90,15 +254,256 @@ where
     }
 }
 
+/// Parser that reads [`Operation`]s from a reader one chunk at a time.
+///
+/// Only the part of the input that has not been scanned yet is kept in memory.
+/// If an [`Operation`] straddles a chunk boundary, the next chunk is read before
+/// attempting to parse it again, unless the [longest instruction](Grammar::max_instruction_len)
+/// is already buffered, in which case no operation starts at that position. So the buffer never
+/// holds more than a chunk besides the longest operation.
+pub struct StreamParser<R, F> {
+    reader: R,
+    ops_parser: F,
+    /// Bytes read from `reader` that have not been discarded yet.
+    buffer: Vec<u8>,
+    /// Scanning position in `buffer`.
+    position: usize,
+    /// Whether the reader has reached the end of input.
+    eof: bool,
+    /// Length of the longest operation.
+    lookahead: usize,
+}
+
+impl<R, F> StreamParser<R, F>
+where
+    R: BufRead,
+{
+    /// Create a parser over `reader`. The size of the chunks is determined by the
+    /// buffer capacity of the reader.
+    /// The provided `ops_parser` is used to attempt to parse an [`Operation`] at every
+    /// position of the input, like in [`with_ops_parser`]. It must not accept operations
+    /// longer than the longest instruction of `grammar`.
+    pub fn new<T>(reader: R, grammar: Grammar, ops_parser: F) -> Self
+    where
+        F: Fn(&[u8]) -> IResult<&[u8], Operation<T>>,
+    {
+        StreamParser {
+            reader,
+            ops_parser,
+            buffer: Vec::new(),
+            position: 0,
+            eof: false,
+            lookahead: grammar.max_instruction_len(),
+        }
+    }
+
+    /// Discard scanned bytes and append the next chunk of input to the buffer.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        self.buffer.drain(..self.position);
+        self.position = 0;
+        let chunk = self.reader.fill_buf()?;
+        let len = chunk.len();
+        self.buffer.extend_from_slice(chunk);
+        self.reader.consume(len);
+        self.eof = len == 0;
+        Ok(())
+    }
+}
+
+impl<R, F, T> Iterator for StreamParser<R, F>
+where
+    R: BufRead,
+    F: Fn(&[u8]) -> IResult<&[u8], Operation<T>>,
+{
+    type Item = io::Result<Operation<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let input = &self.buffer[self.position..];
+            let parsed = match input.is_empty() {
+                true => Err(nom::Err::Incomplete(nom::Needed::Unknown)),
+                false => (self.ops_parser)(input),
+            };
+            match parsed {
+                Ok((remainder, op)) => {
+                    self.position = self.buffer.len() - remainder.len();
+                    return Some(Ok(op));
+                }
+                // The operation may continue in the next chunk, unless it is already longer
+                // than any operation.
+                Err(nom::Err::Incomplete(_)) if !self.eof && input.len() < self.lookahead => {
+                    if let Err(e) = self.read_chunk() {
+                        return Some(Err(e));
+                    }
+                }
+                _ if input.is_empty() => return None,
+                // Skip one byte and attempt to parse at next iteration.
+                _ => self.position += 1,
+            }
+        }
+    }
+}
+
+/// Reason why a substring of the input failed to parse as an [`Operation`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MissReason {
+    /// The substring only parses if these additional syntax variations are allowed.
+    Grammar(Grammar),
+    /// Parsing failed where the described token was expected.
+    Expected(&'static str),
+    /// The input ended before the instruction was complete.
+    Truncated,
+}
+
+impl Display for MissReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissReason::Grammar(grammar) => write!(f, "requires {grammar}"),
+            MissReason::Expected(expected) => write!(f, "expected {expected}"),
+            MissReason::Truncated => write!(f, "input ends mid-instruction"),
+        }
+    }
+}
+
+/// Substring of the input that starts with an instruction keyword, but does not parse
+/// as an [`Operation`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct NearMiss<'a> {
+    /// Offset of the substring from the start of the input.
+    pub offset: usize,
+    /// The substring, up to and including the byte where parsing failed.
+    pub text: &'a [u8],
+    pub reason: MissReason,
+}
+
+impl Display for NearMiss<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: `{}` ({})",
+            self.offset,
+            self.text.escape_ascii(),
+            self.reason
+        )
+    }
+}
+
+/// Find the minimal set of syntax variations in addition to `grammar` that make `i` parse.
+/// Returns the variations and the parsed substring, or `None` if `i` does not parse
+/// even with every variation allowed.
+fn required_grammar<T>(grammar: Grammar, i: &[u8]) -> Option<(Grammar, &[u8])>
+where
+    T: FromStr,
+{
+    let parses = |extra: Grammar| {
+        any_op::<T, nom::error::Error<_>>(grammar.union(extra), i)
+            .ok()
+            .map(|(remainder, _)| &i[..i.len() - remainder.len()])
+    };
+    let lenient = Grammar {
+        max_digits: grammar.max_digits,
+        max_whitespace: grammar.max_whitespace,
+        ..Grammar::LENIENT
+    };
+    parses(lenient)?;
+    // Drop every variation that is not needed to parse.
+    let drops: [fn(&mut Grammar); 3] = [
+        |g| g.whitespace = false,
+        |g| g.signed = false,
+        |g| g.case_insensitive = false,
+    ];
+    let required = drops.into_iter().fold(lenient, |required, drop| {
+        let mut candidate = required;
+        drop(&mut candidate);
+        match parses(candidate) {
+            Some(_) => candidate,
+            None => required,
+        }
+    });
+    parses(required).map(|text| (required, text))
+}
+
+/// Find the reason parsing `i` failed and the remaining input at the point of failure.
+/// Reports the failure of the operation parser that got the furthest.
+/// Returns `None` if no instruction keyword matches at the start of `i`.
+fn failure_reason<T>(grammar: Grammar, i: &[u8]) -> Option<(MissReason, &[u8])>
+where
+    T: FromStr,
+{
+    type Parser<'a, T> =
+        fn(Grammar, &'a [u8]) -> IResult<&'a [u8], Operation<T>, VerboseError<&'a [u8]>>;
+    let parsers: [(Parser<T>, &str); 3] = [(mul_op, "mul"), (do_op, "do"), (dont_op, "don't")];
+    parsers
+        .into_iter()
+        .filter_map(|(parser, keyword)| match parser(grammar, i) {
+            Err(nom::Err::Error(e)) => e.errors.into_iter().find_map(|(i, kind)| match kind {
+                VerboseErrorKind::Context(expected) => Some((MissReason::Expected(expected), i)),
+                _ => None,
+            }),
+            // Input ended after the keyword.
+            Err(nom::Err::Incomplete(_)) if i.len() >= keyword.len() => {
+                Some((MissReason::Truncated, &i[i.len()..]))
+            }
+            _ => None,
+        })
+        .min_by_key(|(_, remainder)| remainder.len())
+}
+
+/// Find substrings of the input that almost parsed as an [`Operation`] under the provided
+/// [`Grammar`], along with the reason they failed to parse.
+///
+/// A near miss is any position where an instruction keyword matches (ignoring case),
+/// but no [`Operation`] can be parsed.
+pub fn near_misses<T>(input: &[u8], grammar: Grammar) -> Vec<NearMiss<'_>>
+where
+    T: FromStr,
+{
+    let mut misses = Vec::new();
+    let mut offset = 0;
+    while offset < input.len() {
+        let i = &input[offset..];
+        if let Ok((remainder, _)) = any_op::<T, nom::error::Error<_>>(grammar, i) {
+            // Not a miss - skip the operation.
+            offset = input.len() - remainder.len();
+            continue;
+        }
+        if let Some((required, text)) = required_grammar::<T>(grammar, i) {
+            misses.push(NearMiss {
+                offset,
+                text,
+                reason: MissReason::Grammar(required),
+            });
+            offset += text.len();
+            continue;
+        }
+        if let Some((reason, remainder)) = failure_reason::<T>(grammar.union(Grammar::LENIENT), i) {
+            // Include the byte that failed to parse, if any.
+            let end = (i.len() - remainder.len() + 1).min(i.len());
+            misses.push(NearMiss {
+                offset,
+                text: &i[..end],
+                reason,
+            });
+        }
+        offset += 1;
+    }
+    misses
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Parse any [`Operation`] with the exact syntax.
+    fn exact_any_op<T: FromStr>(i: &[u8]) -> IResult<&[u8], Operation<T>> {
+        any_op(Grammar::default(), i)
+    }
+
     /// Verify [`mul_op`] parsing works.
     #[test]
     fn test_mul() {
         assert_eq!(
-            any_op("mul(1,2)").unwrap().1,
+            exact_any_op(b"mul(1,2)").unwrap().1,
             Operation::Mul { left: 1, right: 2 }
         );
     }
@@ -106,12 +511,183 @@ mod tests {
     /// Verify [`do_op`] parsing works.
     #[test]
     fn test_do() {
-        assert_eq!(any_op::<u32>("do()").unwrap().1, Operation::Do);
+        assert_eq!(exact_any_op::<u32>(b"do()").unwrap().1, Operation::Do);
     }
 
     /// Verify [`dont_op`] parsing works.
     #[test]
     fn test_dont() {
-        assert_eq!(any_op::<u32>("don't()").unwrap().1, Operation::Dont);
+        assert_eq!(exact_any_op::<u32>(b"don't()").unwrap().1, Operation::Dont);
+    }
+
+    /// Verify that operations embedded in invalid UTF-8 are still parsed.
+    #[test]
+    fn test_invalid_utf8() {
+        let input = b"\xff\xfemul(2,3)\xc3(don't()\x80mul(4,5)\xf0\x9fdo()mul(1,1)\xff";
+        assert_eq!(
+            with_ops_parser::<u32, _>(input, exact_any_op),
+            [
+                Operation::Mul { left: 2, right: 3 },
+                Operation::Dont,
+                Operation::Mul { left: 4, right: 5 },
+                Operation::Do,
+                Operation::Mul { left: 1, right: 1 },
+            ]
+        );
+    }
+
+    /// Verify that syntax variations only parse when allowed by the [`Grammar`].
+    #[test]
+    fn test_grammar() {
+        let input = b"mul( 1 , 2 )MUL(3,4)mul(-5,+6)Don't()";
+        let ops = |grammar| with_ops_parser::<i64, _>(input, |i| any_op(grammar, i));
+        assert_eq!(ops(Grammar::default()), []);
+        assert_eq!(
+            ops(Grammar::LENIENT),
+            [
+                Operation::Mul { left: 1, right: 2 },
+                Operation::Mul { left: 3, right: 4 },
+                Operation::Mul { left: -5, right: 6 },
+                Operation::Dont,
+            ]
+        );
+        let whitespace = Grammar {
+            whitespace: true,
+            ..Default::default()
+        };
+        assert_eq!(ops(whitespace), [Operation::Mul { left: 1, right: 2 }]);
+    }
+
+    /// Verify that whitespace beyond the limit of the grammar is a near miss.
+    #[test]
+    fn test_whitespace_limit() {
+        let spaces = |count| format!("mul({}1,2)", " ".repeat(count));
+        let grammar = Grammar {
+            whitespace: true,
+            ..Default::default()
+        };
+        let input = spaces(DEFAULT_MAX_WHITESPACE);
+        let ops = with_ops_parser::<u32, _>(input.as_bytes(), |i| any_op(grammar, i));
+        assert_eq!(ops, [Operation::Mul { left: 1, right: 2 }]);
+        let input = spaces(DEFAULT_MAX_WHITESPACE + 1);
+        let ops = with_ops_parser::<u32, _>(input.as_bytes(), |i| any_op(grammar, i));
+        assert_eq!(ops, []);
+        let misses = near_misses::<u32>(input.as_bytes(), grammar);
+        let reasons: Vec<_> = misses.iter().map(|miss| miss.reason).collect();
+        assert_eq!(reasons, [MissReason::Expected("operand")]);
+        let grammar = Grammar {
+            max_whitespace: DEFAULT_MAX_WHITESPACE + 1,
+            ..grammar
+        };
+        let ops = with_ops_parser::<u32, _>(input.as_bytes(), |i| any_op(grammar, i));
+        assert_eq!(ops, [Operation::Mul { left: 1, right: 2 }]);
+        assert_eq!(near_misses::<u32>(input.as_bytes(), grammar), []);
+    }
+
+    /// Verify that near misses are reported with the reason they failed to parse.
+    #[test]
+    fn test_near_misses() {
+        let input = b"xmul(2,4)&mul[3,7]!mul(32,64]MUL(1,2)mul( +1,2)do_mul(1234,5)mul(999,256)";
+        let misses = near_misses::<u32>(input, Grammar::default());
+        let reasons = misses
+            .iter()
+            .map(|miss| (miss.text, miss.reason))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            [
+                (&b"mul["[..], MissReason::Expected("`(`")),
+                (b"mul(32,64]", MissReason::Expected("`)`")),
+                (
+                    b"MUL(1,2)",
+                    MissReason::Grammar(Grammar {
+                        case_insensitive: true,
+                        ..Default::default()
+                    })
+                ),
+                (
+                    b"mul( +1,2)",
+                    MissReason::Grammar(Grammar {
+                        whitespace: true,
+                        signed: true,
+                        ..Default::default()
+                    })
+                ),
+                (b"do_", MissReason::Expected("`(`")),
+                (b"mul(1234", MissReason::Expected("`,`")),
+            ]
+        );
+        assert_eq!(misses[0].offset, 10);
+        let misses = near_misses::<u8>(input, Grammar::default());
+        let last = misses.last().map(|miss| (miss.text, miss.reason));
+        assert_eq!(
+            last,
+            Some((&b"mul(9"[..], MissReason::Expected("operand in range")))
+        );
+    }
+
+    /// Verify that an instruction cut off by the end of input is reported as truncated.
+    #[test]
+    fn test_near_miss_truncated() {
+        let misses = near_misses::<u32>(b"mul(1,2)mul(3,4", Grammar::default());
+        assert_eq!(misses.len(), 1);
+        assert_eq!(misses[0].text, b"mul(3,4");
+        assert_eq!(misses[0].reason, MissReason::Truncated);
+    }
+
+    /// Verify that [`StreamParser`] parses the same operations as [`with_ops_parser`]
+    /// regardless of where chunk boundaries fall.
+    #[test]
+    fn test_stream_chunk_boundaries() {
+        let input: &[u8] =
+            b"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))mul(1,";
+        let grammar = Grammar::LENIENT;
+        let expected = with_ops_parser::<u32, _>(input, |i| any_op(grammar, i));
+        for chunk_size in 1..=input.len() + 1 {
+            let reader = io::BufReader::with_capacity(chunk_size, input);
+            let ops = StreamParser::new(reader, grammar, |i| any_op::<u32, _>(grammar, i))
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(ops, expected, "chunk size {chunk_size}");
+        }
+    }
+
+    /// Verify that [`StreamParser`] only buffers a bounded lookahead on long runs that
+    /// could start an instruction, or on parsers that always need more input.
+    #[test]
+    fn test_stream_bounded_buffer() {
+        const CHUNK_SIZE: usize = 64;
+        let bound = 2 * (Grammar::LENIENT.max_instruction_len() + CHUNK_SIZE);
+        let mut digits = b"mul(".to_vec();
+        digits.resize(2 << 20, b'1');
+        digits.extend_from_slice(b",2)mul(3,4)");
+        let mut spaces = b"mul(".to_vec();
+        spaces.resize(2 << 20, b' ');
+        spaces.extend_from_slice(b"mul( 3 , 4 )");
+        for input in [digits, spaces] {
+            let reader = io::BufReader::with_capacity(CHUNK_SIZE, &input[..]);
+            let mut parser = StreamParser::new(reader, Grammar::LENIENT, |i| {
+                any_op::<u32, _>(Grammar::LENIENT, i)
+            });
+            let ops = parser.by_ref().collect::<io::Result<Vec<_>>>().unwrap();
+            assert_eq!(ops, [Operation::Mul { left: 3, right: 4 }]);
+            assert!(
+                parser.buffer.capacity() <= bound,
+                "{}",
+                parser.buffer.capacity()
+            );
+        }
+        let input = vec![b'x'; 2 << 20];
+        let reader = io::BufReader::with_capacity(CHUNK_SIZE, &input[..]);
+        fn incomplete(_: &[u8]) -> IResult<&[u8], Operation<u32>> {
+            Err(nom::Err::Incomplete(nom::Needed::Unknown))
+        }
+        let mut parser = StreamParser::new(reader, Grammar::default(), incomplete);
+        assert!(parser.next().is_none());
+        assert!(
+            parser.buffer.capacity() <= bound,
+            "{}",
+            parser.buffer.capacity()
+        );
     }
 }
@@ -0,0 +1,213 @@
+//! Interactive mode for executing instructions by hand.
+//!
+//! Every line that is not a command is scanned for operations with [`parser::any_op`].
+//! The operations are executed one by one against a persistent [`Interpreter`],
+//! printing its state after each step.
+
+use crate::{interpreter::Interpreter, parser, ExecutionError, Grammar, Operation};
+use num::{CheckedAdd, CheckedMul, Zero};
+use std::{
+    fmt::{Debug, Display},
+    fs,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+const HELP: &str = "\
+commands:
+  :reset         reset the machine state and history
+  :history       show executed instructions
+  :load <path>   execute all instructions in a file
+  :help          show this message
+  :quit          exit the repl
+any other line is scanned for instructions to execute";
+
+/// Interactive instruction machine.
+pub struct Repl<T> {
+    interpreter: Interpreter<T>,
+    /// Operations executed since the last reset.
+    history: Vec<Operation<T>>,
+    grammar: Grammar,
+}
+
+impl<T> Repl<T>
+where
+    T: FromStr + CheckedMul + CheckedAdd + Zero + Clone + Display + Debug,
+{
+    /// Create a repl that parses instructions according to `grammar`.
+    pub fn new(grammar: Grammar) -> Self {
+        Repl {
+            interpreter: Interpreter::new(),
+            history: Vec::new(),
+            grammar,
+        }
+    }
+
+    /// Read lines from `input` and write responses to `output` until the end of input
+    /// or until `:quit` is entered.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        writeln!(output, "type `:help` for a list of commands")?;
+        write!(output, "> ")?;
+        output.flush()?;
+        for line in input.lines() {
+            let line = line?;
+            match line.trim() {
+                ":quit" => return Ok(()),
+                line => self.eval(line, &mut output)?,
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        writeln!(output)
+    }
+
+    /// Evaluate a single line of input.
+    fn eval(&mut self, line: &str, output: &mut impl Write) -> io::Result<()> {
+        match line.split_once(' ').unwrap_or((line, "")) {
+            (":reset", _) => {
+                *self = Repl::new(self.grammar);
+                self.print_state(output)
+            }
+            (":history", _) => {
+                if self.history.is_empty() {
+                    writeln!(output, "no instructions executed")?;
+                }
+                self.history
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(index, operation)| writeln!(output, "#{index} {operation:?}"))
+            }
+            (":load", path) => match fs::read(path.trim()) {
+                Ok(bytes) => self.load(&bytes, output),
+                Err(e) => writeln!(output, "file read io error: {e}"),
+            },
+            (":help", _) => writeln!(output, "{HELP}"),
+            (command, _) if command.starts_with(':') => {
+                writeln!(output, "unknown command `{command}`, type `:help` for help")
+            }
+            _ => self.execute_line(line.as_bytes(), output),
+        }
+    }
+
+    /// Execute every operation found in `line`, printing the state after each step.
+    fn execute_line(&mut self, line: &[u8], output: &mut impl Write) -> io::Result<()> {
+        let grammar = self.grammar;
+        let operations = parser::with_ops_parser(line, |i| parser::any_op::<T, _>(grammar, i));
+        if operations.is_empty() {
+            return writeln!(output, "no instructions found");
+        }
+        for operation in operations {
+            match self.step(operation) {
+                Ok(operation) => write!(output, "{operation:?} => ")?,
+                Err(e) => return writeln!(output, "execution failed: {e}"),
+            }
+            self.print_state(output)?;
+        }
+        Ok(())
+    }
+
+    /// Execute every operation found in the contents of a file, stopping at the first
+    /// that fails, and print how many were executed and the resulting state.
+    fn load(&mut self, bytes: &[u8], output: &mut impl Write) -> io::Result<()> {
+        let grammar = self.grammar;
+        let operations = parser::with_ops_parser(bytes, |i| parser::any_op::<T, _>(grammar, i));
+        let count = operations.len();
+        let mut loaded = 0;
+        for operation in operations {
+            if let Err(e) = self.step(operation) {
+                writeln!(output, "execution failed: {e}")?;
+                break;
+            }
+            loaded += 1;
+        }
+        writeln!(output, "loaded {loaded} of {count} instructions")?;
+        self.print_state(output)
+    }
+
+    /// Execute an operation and record it in the history if successful.
+    fn step(&mut self, operation: Operation<T>) -> Result<&Operation<T>, ExecutionError<T>> {
+        self.interpreter.step(&operation)?;
+        self.history.push(operation);
+        Ok(self.history.last().expect("operation was just pushed"))
+    }
+
+    /// Print whether `mul` is enabled, the sum so far and the number of executed operations.
+    fn print_state(&self, output: &mut impl Write) -> io::Result<()> {
+        let Interpreter {
+            enabled,
+            sum,
+            executed,
+        } = &self.interpreter;
+        writeln!(
+            output,
+            "enabled: {enabled}, sum: {sum}, executed: {executed}"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Repl;
+    use crate::Grammar;
+
+    /// Run the repl on a script and return its output.
+    fn run(script: &str) -> String {
+        let mut output = Vec::new();
+        Repl::<u32>::new(Grammar::default())
+            .run(script.as_bytes(), &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// Verify that state persists between lines and is printed after each step.
+    #[test]
+    fn persistent_state() {
+        let output = run("mul(2,4)\ndon't()mul(5,5)\ndo()\nmul(3,3)\n");
+        let states = output
+            .lines()
+            .filter_map(|line| line.split_once(" => ").map(|(_, state)| state))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            states,
+            [
+                "enabled: true, sum: 8, executed: 1",
+                "enabled: false, sum: 8, executed: 2",
+                "enabled: false, sum: 8, executed: 3",
+                "enabled: true, sum: 8, executed: 4",
+                "enabled: true, sum: 17, executed: 5",
+            ]
+        );
+    }
+
+    /// Verify the `:reset`, `:history` and `:load` commands.
+    #[test]
+    fn commands() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/sample_input_any.txt");
+        let output = run(&format!(
+            "mul(1,1)\n:reset\ndo()\n:history\n:load {path}\n:bogus\n:quit\nmul(1,1)\n"
+        ));
+        assert!(output.contains("> enabled: true, sum: 0, executed: 0\n"));
+        assert!(output.contains("> #0 Do\n"));
+        assert!(
+            output.contains("loaded 6 of 6 instructions\nenabled: true, sum: 48, executed: 7\n")
+        );
+        assert!(output.contains("unknown command `:bogus`"));
+        // Input after `:quit` is ignored.
+        assert_eq!(output.matches("Mul").count(), 1);
+    }
+
+    /// Verify that loading stops at an instruction that fails and reports how many ran.
+    #[test]
+    fn load_overflow() {
+        let mut output = Vec::new();
+        let mut repl = Repl::<u16>::new(Grammar::default());
+        let script = b"mul(2,3)mul(4,5)mul(999,999)mul(1,1)do()mul(6,7)";
+        repl.load(script, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("execution failed: "));
+        assert!(
+            output.ends_with("loaded 2 of 6 instructions\nenabled: true, sum: 26, executed: 2\n")
+        );
+        assert_eq!(repl.history.len(), 2);
+    }
+}
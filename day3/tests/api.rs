@@ -0,0 +1,26 @@
+//! Tests of the public API of day 3.
+
+use day3::{execute_stream, parser::Grammar, solve, Backend, Day3};
+use solution::Solution;
+
+const SAMPLE: &[u8] = include_bytes!("../sample_input_any.txt");
+
+/// Verify that solving, streaming and the solution agree on the sample.
+#[test]
+fn solve_sample() {
+    let solved = solve::<u64>(SAMPLE, Grammar::default());
+    let streamed = execute_stream::<u64>(SAMPLE, Grammar::default()).unwrap();
+    assert_eq!(streamed, solved);
+    let (mul_only, all) = solved;
+    let input = Day3::parse(SAMPLE);
+    assert_eq!(Day3::part1(&input), mul_only.unwrap().to_string());
+    assert_eq!(Day3::part2(&input), all.unwrap().to_string());
+}
+
+/// Verify that backends are parsed from their names.
+#[test]
+fn backend_names() {
+    assert_eq!("u32".parse(), Ok(Backend::U32));
+    assert_eq!("bigint".parse(), Ok(Backend::BigInt));
+    assert!("f64".parse::<Backend>().is_err());
+}
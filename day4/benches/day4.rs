@@ -0,0 +1,10 @@
+//! Benchmarks of day 4 on generated input.
+
+use day4::Day4;
+use generate::{Seeded, DEFAULT_SEED};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let generate = |size| generate::day4::input(&mut Seeded::from_env_or(DEFAULT_SEED), size);
+    bench::main::<Day4>(generate, generate::day4::DEFAULT_SIZE)
+}
@@ -0,0 +1,98 @@
+//! Day 4: Ceres Search.
+//!
+//! The input is a square word search grid. [`xmas`] solves part 1 by counting `xmas` along
+//! every [axis](transform) of the grid, and [`x_mas`] solves part 2 by counting crossing
+//! `mas` pairs. Both operate on the lowercase grid and its [dimensions](input_dimensions).
+
+use solution::Solution;
+
+pub mod transform;
+pub mod x_mas;
+pub mod xmas;
+
+/// Calculate width and height of input.
+///
+/// # Panics
+/// Panics if the input is empty or not square.
+pub fn input_dimensions(input: &str) -> usize {
+    let width = input.lines().next().expect("input must not be empty").len();
+    let height = input.lines().count();
+    assert_eq!(width, height);
+    width // At this point `width == height`.
+}
+
+/// Solution for day 4.
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    /// Lowercase word search grid and its dimensions.
+    type Input = (String, usize);
+
+    fn parse(input: &[u8]) -> Self::Input {
+        let input = std::str::from_utf8(input)
+            .expect("input must be valid UTF-8")
+            .to_lowercase();
+        let dimensions = input_dimensions(&input);
+        (input, dimensions)
+    }
+
+    fn part1((input, dimensions): &Self::Input) -> String {
+        xmas::word_search_xmas_count(input, *dimensions).to_string()
+    }
+
+    fn part2((input, dimensions): &Self::Input) -> String {
+        x_mas::word_search_x_mas_count(input, *dimensions).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{input_dimensions, transform, Day4};
+    use generate::Seeded;
+    use solution::Solution;
+
+    solution::sample_tests!(crate::Day4);
+
+    /// Assert conversion to vertical axis works.
+    #[test]
+    fn vertical_axis() {
+        let input = include_str!("../test_input.txt").to_lowercase();
+        let expected = ["147", "258", "369"];
+        let dimensions = input_dimensions(&input);
+        let axis = transform::vertical(&input, dimensions);
+        assert!(axis.iter().all(|d| expected.contains(&d.as_str())));
+    }
+
+    /// Assert conversion to falling diagonal axis works.
+    #[test]
+    fn falling_diagonal_axis() {
+        let input = include_str!("../test_input.txt").to_lowercase();
+        let expected = ["7", "48", "159", "26", "3"];
+        let dimensions = input_dimensions(&input);
+        let axis = transform::falling_diagonal(&input, dimensions);
+        assert!(axis.iter().all(|d| expected.contains(&d.as_str())));
+    }
+
+    /// Assert conversion to rising diagonal axis works.
+    #[test]
+    fn rising_diagonal_axis() {
+        let input = include_str!("../test_input.txt").to_lowercase();
+        let expected = ["1", "42", "753", "86", "9"];
+        let dimensions = input_dimensions(&input);
+        let axis = transform::rising_diagonal(&input, dimensions);
+        assert!(axis.iter().all(|d| expected.contains(&d.as_str())));
+    }
+
+    /// Test both parts on a large generated grid with planted words.
+    #[test]
+    fn planted() {
+        let mut rng = Seeded::from_env();
+        // Input dimensions must be square.
+        let planted = generate::day4::planted(&mut rng, 100, 100, 300, 100);
+        let input = Day4::parse(planted.input.as_bytes());
+        assert_eq!(Day4::part1(&input), planted.xmas.to_string());
+        assert_eq!(Day4::part2(&input), planted.x_mas.to_string());
+    }
+}
@@ -3,12 +3,12 @@
 //! of some additional memory allocation.
 
 /// Convert input to the horizontal axis (in lines/rows).
-pub(crate) fn horizontal(input: &str) -> impl Iterator<Item = impl AsRef<str> + '_> {
+pub fn horizontal(input: &str) -> impl Iterator<Item = impl AsRef<str> + '_> {
     input.lines()
 }
 
 /// Convert input to the vertical axis (in columns).
-pub(crate) fn vertical(input: &str, dimensions: usize) -> Vec<String> {
+pub fn vertical(input: &str, dimensions: usize) -> Vec<String> {
     let mut columns = Vec::with_capacity(dimensions);
     (0..dimensions).for_each(|_| columns.push(String::with_capacity(dimensions)));
     columns
@@ -43,8 +43,35 @@ where
     })
 }
 
+/// Row and column where the falling or rising diagonal `diagonal_ix` starts, 0-based.
+fn diagonal_start(diagonal_ix: usize, dimensions: usize) -> (usize, usize) {
+    let row = diagonal_ix.min(dimensions - 1);
+    let column = diagonal_ix.max(dimensions - 1) + 1 - dimensions;
+    (row, column)
+}
+
+/// Row and column of the character at `index` in the falling diagonal `diagonal_ix`, 0-based.
+pub(crate) fn falling_diagonal_position(
+    diagonal_ix: usize,
+    index: usize,
+    dimensions: usize,
+) -> (usize, usize) {
+    let (row, column) = diagonal_start(diagonal_ix, dimensions);
+    (dimensions - 1 - row + index, column + index)
+}
+
+/// Row and column of the character at `index` in the rising diagonal `diagonal_ix`, 0-based.
+pub(crate) fn rising_diagonal_position(
+    diagonal_ix: usize,
+    index: usize,
+    dimensions: usize,
+) -> (usize, usize) {
+    let (row, column) = diagonal_start(diagonal_ix, dimensions);
+    (row - index, column + index)
+}
+
 /// Convert input to the falling diagonal axis.
-pub(crate) fn falling_diagonal(input: &str, dimensions: usize) -> Vec<String> {
+pub fn falling_diagonal(input: &str, dimensions: usize) -> Vec<String> {
     // Calculate diagonal lengths and count.
     let diagonals_count = diagonals_count(dimensions);
     let diagonal_lenghts: Vec<_> = diagonal_lenghts(dimensions).collect();
@@ -76,7 +103,7 @@ pub(crate) fn falling_diagonal(input: &str, dimensions: usize) -> Vec<String> {
 }
 
 /// Convert input to the rising diagonal axis.
-pub(crate) fn rising_diagonal(input: &str, dimensions: usize) -> Vec<String> {
+pub fn rising_diagonal(input: &str, dimensions: usize) -> Vec<String> {
     // Calculate diagonal lengths and count.
     let diagonals_count = diagonals_count(dimensions);
     let diagonal_lenghts: Vec<_> = diagonal_lenghts(dimensions).collect();
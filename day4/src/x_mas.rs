@@ -1,18 +1,39 @@
 //! Part 2 implementation.
 
 use crate::transform;
+use std::collections::HashSet;
 
 /// Find all occurences of `X-MAS` (two `MAS` in the shape of an `X`).
 ///
 /// # Naming convention
 /// Very big difference... See [`word_search_xmas_count`](crate::xmas::word_search_xmas_count)
 /// and <https://adventofcode.com/2024/day/4> (part 2).
-pub(super) fn word_search_x_mas_count(input: &str, dimensions: usize) -> u32 {
-    // Build axes from input.
-    let horizontal = transform::horizontal(input);
-    let vertical = transform::vertical(input, dimensions);
+pub fn word_search_x_mas_count(input: &str, dimensions: usize) -> u32 {
+    // Build axes from input. Only diagonals can form an `X`.
     let diagonal_falling = transform::falling_diagonal(input, dimensions);
     let diagonal_rising = transform::rising_diagonal(input, dimensions);
+    // An `X-MAS` is centered where a falling and a rising `mas` share their `a`.
+    let falling_centers: HashSet<_> = mas_centers(&diagonal_falling)
+        .map(|(diagonal_ix, index)| {
+            transform::falling_diagonal_position(diagonal_ix, index, dimensions)
+        })
+        .collect();
+    mas_centers(&diagonal_rising)
+        .map(|(diagonal_ix, index)| {
+            transform::rising_diagonal_position(diagonal_ix, index, dimensions)
+        })
+        .filter(|center| falling_centers.contains(center))
+        .count() as u32
+}
 
-    todo!()
+/// Find the centers of all `mas` and `sam` occurrences in an axis.
+/// Returns the index of each line and the index of the `a` in that line.
+fn mas_centers(lines: &[String]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    lines.iter().enumerate().flat_map(|(line_ix, line)| {
+        line.as_bytes()
+            .windows(3)
+            .enumerate()
+            .filter(|(_, window)| matches!(window, [b'm', b'a', b's'] | [b's', b'a', b'm']))
+            .map(move |(index, _)| (line_ix, index + 1))
+    })
 }
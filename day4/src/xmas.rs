@@ -18,7 +18,7 @@ fn samx(i: &str) -> nom::IResult<&str, ()> {
 /// Directionality of the word is irrelevant - both `xmas` and `samx` are counted.
 ///
 /// `dimensions` must match input height (lines/rows) and width (chars/columns).
-pub(super) fn word_search_xmas_count(input: &str, dimensions: usize) -> u32 {
+pub fn word_search_xmas_count(input: &str, dimensions: usize) -> u32 {
     // Build axes from input.
     let horizontal = transform::horizontal(input);
     let vertical = transform::vertical(input, dimensions);
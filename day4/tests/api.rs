@@ -0,0 +1,36 @@
+//! Tests of the public API of day 4.
+
+use day4::{input_dimensions, transform, x_mas, xmas};
+use solution::samples::Manifest;
+use std::path::Path;
+
+const SAMPLE: &str = include_str!("../sample_input.txt");
+
+/// Verify that the library functions on the lowercase sample find the answers listed in the
+/// sample manifest.
+#[test]
+fn word_search() {
+    let manifest = Manifest::load(Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap();
+    let sample = manifest
+        .samples
+        .iter()
+        .find(|sample| sample.file == Path::new("sample_input.txt"))
+        .expect("sample is listed in the manifest");
+    let expected = |answer: &Option<_>| answer.as_ref().map(ToString::to_string);
+    let input = SAMPLE.to_lowercase();
+    let dimensions = input_dimensions(&input);
+    assert_eq!(dimensions, SAMPLE.lines().count());
+    assert_eq!(transform::vertical(&input, dimensions).len(), dimensions);
+    assert_eq!(
+        transform::falling_diagonal(&input, dimensions).len(),
+        2 * dimensions - 1
+    );
+    assert_eq!(
+        Some(xmas::word_search_xmas_count(&input, dimensions).to_string()),
+        expected(&sample.part1)
+    );
+    assert_eq!(
+        Some(x_mas::word_search_x_mas_count(&input, dimensions).to_string()),
+        expected(&sample.part2)
+    );
+}
@@ -0,0 +1,16 @@
+//! Inputs of day 1: two columns of location IDs.
+
+use rand::Rng;
+
+/// Number of lines in the inputs used by benchmarks.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Generate `lines` pairs of location IDs.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, lines: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..lines {
+        let (left, right) = (rng.gen::<u16>(), rng.gen::<u16>());
+        input.push_str(&format!("{left} {right}\n"));
+    }
+    input
+}
@@ -0,0 +1,228 @@
+//! Inputs of day 2: reports of levels.
+
+use itertools::Itertools;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    seq::SliceRandom,
+    Rng,
+};
+use std::ops::RangeInclusive;
+
+/// Number of reports in the inputs used by benchmarks.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Generate `reports` reports of 4 to 8 levels.
+/// Adjacent levels differ by at most 5, so a fair share of the reports is safe.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, reports: usize) -> String {
+    let reports = (0..reports)
+        .map(|_| {
+            let len = rng.gen_range(4..=8);
+            let shifts = (0..len).map(|_| rng.gen_range(-5..=5i32)).collect_vec();
+            // Start high enough that levels never go negative.
+            let mut level = shifts
+                .iter()
+                .fold(rng.gen_range(0..=110), |acc, shift| acc + shift.abs());
+            shifts
+                .iter()
+                .map(|shift| {
+                    let old_level = level;
+                    level += shift;
+                    old_level
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+    let mut input = String::new();
+    reports
+        .into_iter()
+        .for_each(|report| input.push_str(&format!("{}\n", report.iter().join(" "))));
+    input
+}
+
+/// Safety of a report.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Label {
+    /// Safe without removing any level (part 1 and 2).
+    Safe,
+    /// Only safe after removing a single level (part 2).
+    Tolerated,
+    /// Not safe even after removing a single level.
+    Unsafe,
+}
+
+impl Label {
+    /// Label of `report`, determined by trying every level removal.
+    pub fn of(report: &[i64]) -> Self {
+        if is_safe(report.iter().copied()) {
+            return Label::Safe;
+        }
+        let tolerated = (0..report.len()).any(|skip| {
+            let levels = report.iter().enumerate().filter(|&(i, _)| i != skip);
+            is_safe(levels.map(|(_, &level)| level))
+        });
+        match tolerated {
+            true => Label::Tolerated,
+            false => Label::Unsafe,
+        }
+    }
+}
+
+/// Whether levels are strictly increasing or decreasing by 1 to 3 at each step.
+/// Reports with fewer than 2 levels are not safe, matching the solution.
+fn is_safe(levels: impl Iterator<Item = i64>) -> bool {
+    let differences: Vec<_> = levels.tuple_windows().map(|(a, b)| b - a).collect();
+    !differences.is_empty()
+        && (differences.iter().all(|d| (1..=3).contains(d))
+            || differences.iter().all(|d| (-3..=-1).contains(d)))
+}
+
+/// Reports with a planted [`Label`] each.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Labelled {
+    /// One report per line.
+    pub input: String,
+    /// Label of each report, in order.
+    pub labels: Vec<Label>,
+}
+
+impl Labelled {
+    /// Number of reports with `label`.
+    pub fn count(&self, label: Label) -> usize {
+        self.labels.iter().filter(|&&l| l == label).count()
+    }
+
+    /// Number of safe reports without removing levels (part 1).
+    pub fn strict_count(&self) -> usize {
+        self.count(Label::Safe)
+    }
+
+    /// Number of safe reports when a single level may be removed (part 2).
+    pub fn tolerant_count(&self) -> usize {
+        self.count(Label::Safe) + self.count(Label::Tolerated)
+    }
+}
+
+/// Generate `reports` reports with lengths in `lengths` and labels picked with the relative
+/// frequencies in `weights`, e.g. `&[(Label::Safe, 2), (Label::Unsafe, 1)]`.
+///
+/// Safe reports are random walks with valid steps. Tolerated reports are safe reports with one
+/// bad level inserted: a repeated level, a jump of more than 3, or a step in the wrong
+/// direction. Unsafe reports are random walks with larger steps. Every report is checked with
+/// [`Label::of`] and regenerated if the construction didn't produce the intended label.
+/// All levels are non-negative.
+///
+/// # Panics
+/// Panics if reports could be shorter than 4 levels, or no label has a positive weight.
+pub fn labelled<R: Rng + ?Sized>(
+    rng: &mut R,
+    reports: usize,
+    lengths: RangeInclusive<usize>,
+    weights: &[(Label, u32)],
+) -> Labelled {
+    assert!(*lengths.start() >= 4, "reports must have at least 4 levels");
+    let labels = WeightedIndex::new(weights.iter().map(|&(_, weight)| weight))
+        .expect("at least one label must have a positive weight");
+    let mut labelled = Labelled {
+        input: String::new(),
+        labels: Vec::with_capacity(reports),
+    };
+    for _ in 0..reports {
+        let label = weights[labels.sample(rng)].0;
+        let len = rng.gen_range(lengths.clone());
+        let report = loop {
+            let report = match label {
+                Label::Safe => safe_report(rng, len),
+                Label::Tolerated => tolerated_report(rng, len),
+                Label::Unsafe => unsafe_report(rng, len),
+            };
+            if Label::of(&report) == label {
+                break report;
+            }
+        };
+        labelled.input.push_str(&report.iter().join(" "));
+        labelled.input.push('\n');
+        labelled.labels.push(label);
+    }
+    labelled
+}
+
+/// Random walk of `len` levels with steps of 1 to 3 in a single direction.
+fn safe_report<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Vec<i64> {
+    let direction = *[-1, 1].choose(rng).unwrap();
+    // Start high enough that levels never go negative.
+    let mut level = rng.gen_range(3 * len as i64..=3 * len as i64 + 60);
+    (0..len)
+        .map(|_| {
+            let old_level = level;
+            level += direction * rng.gen_range(1..=3);
+            old_level
+        })
+        .collect()
+}
+
+/// Safe report with a single bad level inserted, so it has `len` levels.
+fn tolerated_report<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Vec<i64> {
+    let mut report = safe_report(rng, len - 1);
+    let direction = (report[1] - report[0]).signum();
+    let index = rng.gen_range(0..len);
+    // Level the bad level follows, or precedes if inserted first.
+    let (neighbor, direction) = match index {
+        0 => (report[0], -direction),
+        _ => (report[index - 1], direction),
+    };
+    let bad = match rng.gen_range(0..3) {
+        0 => neighbor,
+        1 => neighbor + direction * rng.gen_range(4..=10),
+        _ => neighbor - direction * rng.gen_range(1..=3),
+    };
+    report.insert(index, bad.max(0));
+    report
+}
+
+/// Random walk of `len` levels with steps of up to 6 in either direction.
+fn unsafe_report<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Vec<i64> {
+    let mut level = rng.gen_range(6 * len as i64..=6 * len as i64 + 60);
+    (0..len)
+        .map(|_| {
+            let old_level = level;
+            level += rng.gen_range(-6..=6);
+            old_level
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{labelled, Label};
+    use crate::Seeded;
+
+    /// Verify the labels of the sample reports.
+    #[test]
+    fn label_of() {
+        let labels = [
+            [7, 6, 4, 2, 1],
+            [1, 2, 7, 8, 9],
+            [9, 7, 6, 2, 1],
+            [1, 3, 2, 4, 5],
+            [8, 6, 4, 4, 1],
+            [1, 3, 6, 7, 9],
+        ]
+        .map(|report| Label::of(&report));
+        use Label::*;
+        assert_eq!(labels, [Safe, Unsafe, Unsafe, Tolerated, Tolerated, Safe]);
+    }
+
+    /// Verify that reports follow the requested lengths and proportions.
+    #[test]
+    fn proportions() {
+        let mut rng = Seeded::from_env();
+        let weights = [(Label::Safe, 1), (Label::Tolerated, 0), (Label::Unsafe, 3)];
+        let labelled = labelled(&mut rng, 1000, 5..=6, &weights);
+        assert_eq!(labelled.count(Label::Tolerated), 0);
+        assert!((150..350).contains(&labelled.count(Label::Safe)));
+        assert!(labelled
+            .input
+            .lines()
+            .all(|line| (5..=6).contains(&line.split(' ').count())));
+    }
+}
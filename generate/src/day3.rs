@@ -0,0 +1,118 @@
+//! Inputs of day 3: instructions in corrupted memory.
+
+use rand::{seq::SliceRandom, Rng};
+
+/// Number of instructions in the inputs used by benchmarks.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Characters of the corrupted memory between instructions.
+/// Can't form instructions, since they contain neither `m` nor `d`.
+const CORRUPTED: &[u8] = b"#%&*+,-/:;<=>?@[]^_|~ ()0123456789xyz";
+
+/// Generate `instructions` instructions separated by corrupted memory.
+/// Operands are small, so the sum of the products doesn't overflow `u32`.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, instructions: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..instructions {
+        match rng.gen_range(0..10) {
+            0 => input.push_str("do()"),
+            1 => input.push_str("don't()"),
+            _ => input.push_str(&format!(
+                "mul({},{})",
+                rng.gen_range(1..100),
+                rng.gen_range(1..100)
+            )),
+        }
+        (0..rng.gen_range(0..8)).for_each(|_| input.push(*CORRUPTED.choose(rng).unwrap() as char));
+    }
+    input
+}
+
+/// Instructions cut short or slightly malformed, which are not executed.
+/// Each is followed by a character that can't complete it.
+const NEAR_MISSES: &[&str] = &[
+    "mul(1,2",
+    "mul[3,4]",
+    "mul (5,6)",
+    "mul(7 ,8)",
+    "MUL(2,3)",
+    "mul(-1,2)",
+    "mul(1,2,3)",
+    "mul(,4)",
+    "mul()",
+    "do(",
+    "do ()",
+    "don't",
+    "dont()",
+    "don't[]",
+];
+
+/// Corrupted memory with planted instructions, and the results of executing them.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Planted {
+    pub input: String,
+    /// Sum of all `mul` instructions (part 1).
+    pub part1: u64,
+    /// Sum of the `mul` instructions enabled by `do()` and `don't()` (part 2).
+    pub part2: u64,
+}
+
+/// Generate `instructions` planted instructions and near misses, separated by corrupted
+/// memory.
+///
+/// Besides the [near misses](NEAR_MISSES), an unfinished `mul(a,` is sometimes directly
+/// followed by the next instruction, which must still be found. Operands have 1 to 3 digits
+/// as in the puzzle, and instructions with a 4 digit operand, e.g. `mul(1234,5)`, are
+/// planted as near misses.
+///
+/// Results are only representable by `u32` while `instructions` is below about 10 000.
+pub fn planted<R: Rng + ?Sized>(rng: &mut R, instructions: usize) -> Planted {
+    let mut planted = Planted {
+        input: String::new(),
+        part1: 0,
+        part2: 0,
+    };
+    let mut enabled = true;
+    for _ in 0..instructions {
+        match rng.gen_range(0..20) {
+            0 | 1 => {
+                planted.input.push_str("do()");
+                enabled = true;
+            }
+            2 | 3 => {
+                planted.input.push_str("don't()");
+                enabled = false;
+            }
+            4..=8 => {
+                planted.input.push_str(NEAR_MISSES.choose(rng).unwrap());
+                planted.input.push('x');
+            }
+            9 => {
+                // Unfinished, with the next instruction starting where its operand should.
+                planted
+                    .input
+                    .push_str(&format!("mul({},", rng.gen_range(1..1000)));
+                continue;
+            }
+            10 => {
+                // Too many digits in the left operand.
+                planted.input.push_str(&format!(
+                    "mul({},{})",
+                    rng.gen_range(1000..10000),
+                    rng.gen_range(1..10)
+                ));
+            }
+            _ => {
+                let (left, right) = (rng.gen_range(1..1000), rng.gen_range(1..1000));
+                planted.input.push_str(&format!("mul({left},{right})"));
+                planted.part1 += left * right;
+                if enabled {
+                    planted.part2 += left * right;
+                }
+            }
+        }
+        (0..rng.gen_range(0..8))
+            .for_each(|_| planted.input.push(*CORRUPTED.choose(rng).unwrap() as char));
+    }
+    planted
+}
@@ -0,0 +1,257 @@
+//! Inputs of day 4: square word search grids.
+
+use rand::{seq::SliceRandom, Rng};
+
+/// Number of cells in the inputs used by benchmarks.
+pub const DEFAULT_SIZE: usize = 1024;
+
+/// Generate a square grid of about `cells` random letters of `XMAS`.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, cells: usize) -> String {
+    let side = (cells as f64).sqrt().round() as usize;
+    let mut input = String::with_capacity((side + 1) * side);
+    for _ in 0..side {
+        (0..side).for_each(|_| input.push(*b"XMAS".choose(rng).unwrap() as char));
+        input.push('\n');
+    }
+    input
+}
+
+/// Letters of the grid outside of planted words.
+/// Can't form `XMAS` or `MAS` on their own, since they contain neither `M` nor `A`.
+const FILLER: &[u8] = b"XSBEO";
+
+/// Directions words can be planted in, as row and column steps.
+const DIRECTIONS: [(isize, isize); 8] = [
+    (0, 1),
+    (0, -1),
+    (1, 0),
+    (-1, 0),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Attempts at planting a single word before giving up.
+const ATTEMPTS: usize = 10_000;
+
+/// Word search grid with planted words, and the exact number of words it contains.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Planted {
+    /// Rows of the grid, each followed by a newline.
+    pub input: String,
+    /// Number of `XMAS` in any direction (part 1).
+    pub xmas: u32,
+    /// Number of `X-MAS`, two `MAS` crossing diagonally at their `A` (part 2).
+    pub x_mas: u32,
+}
+
+/// Generate a grid of `rows` by `columns` letters with `words` planted `XMAS` and `crosses`
+/// planted `X-MAS`, in random directions, on top of filler letters that can't form words.
+///
+/// Half of the words are planted across a letter of a previously planted word, so words
+/// overlap. Planted words can also form additional words together, e.g. two `XMAS` crossing
+/// at their `A` form an `X-MAS`, so the counts are determined from the finished grid.
+///
+/// # Panics
+/// Panics if the grid is too small to plant the requested words.
+pub fn planted<R: Rng + ?Sized>(
+    rng: &mut R,
+    rows: usize,
+    columns: usize,
+    words: usize,
+    crosses: usize,
+) -> Planted {
+    let mut grid = Grid {
+        rows,
+        columns,
+        cells: vec![None; rows * columns],
+    };
+    for _ in 0..crosses {
+        let planted = (0..ATTEMPTS).any(|_| {
+            let (row, column) = (rng.gen_range(1..rows - 1), rng.gen_range(1..columns - 1));
+            let mut mas = || *[b"MAS".as_slice(), b"SAM"].choose(rng).unwrap();
+            let falling = (mas(), (row - 1, column - 1), (1, 1));
+            let rising = (mas(), (row + 1, column - 1), (-1, 1));
+            grid.place(&[falling, rising])
+        });
+        assert!(planted, "grid too small to plant {crosses} crosses");
+    }
+    for _ in 0..words {
+        let planted = (0..ATTEMPTS).any(|_| {
+            let direction = *DIRECTIONS.choose(rng).unwrap();
+            let anchor = match rng.gen_bool(0.5) {
+                true => grid.random_planted(rng),
+                false => None,
+            };
+            let start = match anchor {
+                // Start so that a matching letter of the word lands on the planted letter.
+                Some(((row, column), letter)) => {
+                    let index = b"XMAS".iter().position(|&l| l == letter).unwrap() as isize;
+                    let row = row as isize - index * direction.0;
+                    let column = column as isize - index * direction.1;
+                    match (usize::try_from(row), usize::try_from(column)) {
+                        (Ok(row), Ok(column)) => (row, column),
+                        _ => return false,
+                    }
+                }
+                None => (rng.gen_range(0..rows), rng.gen_range(0..columns)),
+            };
+            grid.place(&[(b"XMAS", start, direction)])
+        });
+        assert!(planted, "grid too small to plant {words} words");
+    }
+
+    let letters: Vec<Vec<u8>> = grid
+        .cells
+        .chunks(columns)
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.unwrap_or_else(|| *FILLER.choose(rng).unwrap()))
+                .collect()
+        })
+        .collect();
+    let mut input = String::with_capacity((columns + 1) * rows);
+    letters.iter().for_each(|row| {
+        input.push_str(std::str::from_utf8(row).expect("letters are ASCII"));
+        input.push('\n');
+    });
+    Planted {
+        input,
+        xmas: count_xmas(&letters),
+        x_mas: count_x_mas(&letters),
+    }
+}
+
+/// Word planted at a start cell (row and column), in a direction (row and column step).
+type Placement<'a> = (&'a [u8], (usize, usize), (isize, isize));
+
+/// Grid of planted letters. Cells without a planted letter are `None`.
+struct Grid {
+    rows: usize,
+    columns: usize,
+    cells: Vec<Option<u8>>,
+}
+
+impl Grid {
+    /// Index of the cell `steps` steps in `direction` from `start`, if it's in the grid.
+    fn index(
+        &self,
+        (row, column): (usize, usize),
+        (row_step, column_step): (isize, isize),
+        steps: usize,
+    ) -> Option<usize> {
+        let row = row.checked_add_signed(row_step * steps as isize)?;
+        let column = column.checked_add_signed(column_step * steps as isize)?;
+        (row < self.rows && column < self.columns).then_some(row * self.columns + column)
+    }
+
+    /// Plant each word at its start in its direction, if all of them fit in the grid and
+    /// only overlap planted letters that match. Plants nothing otherwise.
+    fn place(&mut self, words: &[Placement]) -> bool {
+        let mut letters = vec![];
+        for &(word, start, direction) in words {
+            for (steps, &letter) in word.iter().enumerate() {
+                let Some(index) = self.index(start, direction, steps) else {
+                    return false;
+                };
+                letters.push((index, letter));
+            }
+        }
+        let fits = letters.iter().enumerate().all(|(i, &(index, letter))| {
+            self.cells[index].is_none_or(|planted| planted == letter)
+                && letters[..i]
+                    .iter()
+                    .all(|&(other, other_letter)| other != index || other_letter == letter)
+        });
+        if fits {
+            letters
+                .into_iter()
+                .for_each(|(index, letter)| self.cells[index] = Some(letter));
+        }
+        fits
+    }
+
+    /// Random planted cell and its letter, if any cell is planted.
+    fn random_planted<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<((usize, usize), u8)> {
+        let planted: Vec<_> = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.map(|letter| (index, letter)))
+            .collect();
+        let &(index, letter) = planted.choose(rng)?;
+        Some(((index / self.columns, index % self.columns), letter))
+    }
+}
+
+/// Count `XMAS` in any direction by checking every cell and direction.
+fn count_xmas(rows: &[Vec<u8>]) -> u32 {
+    let at = |row: usize, column: usize, (row_step, column_step): (isize, isize), steps: isize| {
+        let row = row.checked_add_signed(row_step * steps)?;
+        let column = column.checked_add_signed(column_step * steps)?;
+        rows.get(row)?.get(column).copied()
+    };
+    let mut count = 0;
+    for (row, letters) in rows.iter().enumerate() {
+        for column in 0..letters.len() {
+            count += DIRECTIONS
+                .iter()
+                .filter(|&&direction| {
+                    (0..4).all(|steps| {
+                        at(row, column, direction, steps) == Some(b"XMAS"[steps as usize])
+                    })
+                })
+                .count() as u32;
+        }
+    }
+    count
+}
+
+/// Count `X-MAS` by checking the diagonals around every `A`.
+fn count_x_mas(rows: &[Vec<u8>]) -> u32 {
+    let mut count = 0;
+    for row in 1..rows.len().saturating_sub(1) {
+        for column in 1..rows[row].len().saturating_sub(1) {
+            if rows[row][column] != b'A' {
+                continue;
+            }
+            let is_mas = |a: u8, b: u8| matches!((a, b), (b'M', b'S') | (b'S', b'M'));
+            let falling = is_mas(rows[row - 1][column - 1], rows[row + 1][column + 1]);
+            let rising = is_mas(rows[row + 1][column - 1], rows[row - 1][column + 1]);
+            count += (falling && rising) as u32;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_x_mas, count_xmas, planted};
+    use crate::Seeded;
+
+    /// Verify that filler letters form no words, and planted words are found.
+    #[test]
+    fn planted_counts() {
+        let mut rng = Seeded::from_env();
+        let empty = planted(&mut rng, 20, 30, 0, 0);
+        assert_eq!((empty.xmas, empty.x_mas), (0, 0));
+        assert_eq!(empty.input.lines().count(), 20);
+        assert!(empty.input.lines().all(|line| line.len() == 30));
+        let single = planted(&mut rng, 20, 30, 1, 1);
+        assert!(single.xmas >= 1 && single.x_mas >= 1, "{single:?}");
+    }
+
+    /// Verify the reference counts on the sample grid.
+    #[test]
+    fn reference_counts() {
+        let sample = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\n\
+            XXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX\n";
+        let rows: Vec<_> = sample
+            .lines()
+            .map(|line| line.as_bytes().to_vec())
+            .collect();
+        assert_eq!(count_xmas(&rows), 18);
+        assert_eq!(count_x_mas(&rows), 9);
+    }
+}
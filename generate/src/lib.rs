@@ -0,0 +1,141 @@
+//! Generators of puzzle inputs for every day, for tests and benchmarks.
+//!
+//! Generators are driven by an explicit seed, so every generated input can be reproduced.
+//! [`Seeded`] prints its seed if the thread panics while it's alive, and its seed can be
+//! overridden with the [`SEED_ENV`] environment variable to rerun a failing test.
+
+use rand::{rngs::StdRng, Error, RngCore, SeedableRng};
+use std::env;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+
+/// Environment variable that overrides the seed of [`Seeded::from_env`] and
+/// [`Seeded::from_env_or`].
+pub const SEED_ENV: &str = "AOC_SEED";
+/// Seed used by benchmarks, so results are comparable between runs.
+pub const DEFAULT_SEED: u64 = 2024;
+
+/// Random number generator that remembers its seed.
+///
+/// Prints the seed when dropped during a panic, e.g. when a test using it fails.
+#[derive(Debug, Clone)]
+pub struct Seeded {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Seeded {
+    /// Create a generator from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Seeded {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Create a generator from the seed in [`SEED_ENV`], or a random seed if it's not set.
+    ///
+    /// # Panics
+    /// Panics if [`SEED_ENV`] is set but is not a valid seed.
+    pub fn from_env() -> Self {
+        Self::new(env_seed().unwrap_or_else(rand::random))
+    }
+
+    /// Create a generator from the seed in [`SEED_ENV`], or `seed` if it's not set.
+    ///
+    /// # Panics
+    /// Panics if [`SEED_ENV`] is set but is not a valid seed.
+    pub fn from_env_or(seed: u64) -> Self {
+        Self::new(env_seed().unwrap_or(seed))
+    }
+
+    /// Seed the generator was created from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// Seed set in [`SEED_ENV`], if any.
+fn env_seed() -> Option<u64> {
+    let seed = env::var(SEED_ENV).ok()?;
+    Some(
+        seed.trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("`{SEED_ENV}` must be an unsigned integer, got `{seed}`")),
+    )
+}
+
+impl Drop for Seeded {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            eprintln!(
+                "generated with seed {} (rerun with `{SEED_ENV}={}`)",
+                self.seed, self.seed
+            );
+        }
+    }
+}
+
+impl RngCore for Seeded {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Generate an input for `day` of about `size`, in the unit used by the day's generator.
+/// Returns `None` if there is no generator for `day`.
+pub fn input(day: u8, rng: &mut impl RngCore, size: usize) -> Option<String> {
+    match day {
+        1 => Some(day1::input(rng, size)),
+        2 => Some(day2::input(rng, size)),
+        3 => Some(day3::input(rng, size)),
+        4 => Some(day4::input(rng, size)),
+        _ => None,
+    }
+}
+
+/// Size of the inputs used by benchmarks of `day`, in the unit used by the day's generator.
+/// Returns `None` if there is no generator for `day`.
+pub fn default_size(day: u8) -> Option<usize> {
+    match day {
+        1 => Some(day1::DEFAULT_SIZE),
+        2 => Some(day2::DEFAULT_SIZE),
+        3 => Some(day3::DEFAULT_SIZE),
+        4 => Some(day4::DEFAULT_SIZE),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{default_size, input, Seeded};
+
+    /// Verify that the same seed generates the same inputs.
+    #[test]
+    fn reproducible() {
+        for day in 1..=4 {
+            let size = default_size(day).unwrap();
+            let first = input(day, &mut Seeded::new(7), size).unwrap();
+            let second = input(day, &mut Seeded::new(7), size).unwrap();
+            let other = input(day, &mut Seeded::new(8), size).unwrap();
+            assert_eq!(first, second, "day {day}");
+            assert_ne!(first, other, "day {day}");
+        }
+        assert_eq!(input(5, &mut Seeded::new(7), 10), None);
+    }
+}
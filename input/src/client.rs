@@ -0,0 +1,250 @@
+//! Client for the Advent of Code website.
+//!
+//! Requests are authenticated with the session cookie of a logged in user.
+//! Downloaded inputs are cached in the [inputs directory](inputs_dir), so each input
+//! is only downloaded once. Inputs [encrypted](crate::crypt) in the inputs directory
+//! are decrypted instead of being downloaded.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+use crate::crypt::{self, CryptError, Key};
+
+/// Environment variable that holds the session cookie.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Environment variable that overrides the [inputs directory](inputs_dir).
+pub const INPUTS_DIR_ENV: &str = "AOC_INPUTS_DIR";
+/// Address of the Advent of Code website.
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// Event year the solutions in this repository are for.
+pub const YEAR: u16 = 2024;
+
+/// Sent with every request, as requested by the puzzle author.
+const USER_AGENT: &str = concat!(
+    "github.com/chipnertkj/aoc (",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+/// Directory downloaded inputs are cached in.
+///
+/// Defaults to `inputs` in the workspace root.
+/// Can be overridden with the [`INPUTS_DIR_ENV`] environment variable.
+pub fn inputs_dir() -> PathBuf {
+    env::var_os(INPUTS_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"))
+}
+
+/// Error returned when a request to the website fails.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("session cookie is not set (set the `{SESSION_ENV}` environment variable)")]
+    MissingSession,
+    #[error("request failed with status {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("request failed: {0}")]
+    Transport(Box<ureq::Transport>),
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("failed to decrypt input: {0}")]
+    Crypt(#[from] CryptError),
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(status, response) => ClientError::Status {
+                status,
+                body: response.into_string().unwrap_or_default(),
+            },
+            ureq::Error::Transport(transport) => ClientError::Transport(Box::new(transport)),
+        }
+    }
+}
+
+/// Client for the Advent of Code website.
+#[derive(Debug, Clone)]
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+    pub(crate) year: u16,
+    pub(crate) inputs_dir: PathBuf,
+    /// Loaded with [`Key::from_env`] when needed if not provided.
+    key: Option<Key>,
+}
+
+impl Client {
+    /// Create a client for [`YEAR`] that uses the session cookie from the
+    /// [`SESSION_ENV`] environment variable and caches inputs in [`inputs_dir`].
+    pub fn from_env() -> Self {
+        Client {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: BASE_URL.to_owned(),
+            session: env::var(SESSION_ENV).ok().filter(|s| !s.trim().is_empty()),
+            year: YEAR,
+            inputs_dir: inputs_dir(),
+            key: None,
+        }
+    }
+
+    /// Send requests to `base_url` instead of [`BASE_URL`].
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Authenticate with the provided session cookie.
+    pub fn with_session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    /// Cache inputs in `inputs_dir` instead of [`inputs_dir`].
+    pub fn with_inputs_dir(mut self, inputs_dir: impl Into<PathBuf>) -> Self {
+        self.inputs_dir = inputs_dir.into();
+        self
+    }
+
+    /// Decrypt inputs with the provided key instead of loading it from the environment.
+    pub fn with_key(mut self, key: Key) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Path the input for `day` is cached at.
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.inputs_dir.join(format!("day{day}.txt"))
+    }
+
+    /// Key used to encrypt and decrypt inputs.
+    pub fn key(&self) -> Result<Key, CryptError> {
+        match &self.key {
+            Some(key) => Ok(key.clone()),
+            None => Key::from_env(),
+        }
+    }
+
+    /// Return the input for `day`.
+    ///
+    /// Reads the cached input if present, otherwise decrypts the [encrypted input](crypt::encrypted_path)
+    /// if present, otherwise downloads the input and caches it.
+    pub fn fetch_input(&self, day: u8) -> Result<String, ClientError> {
+        let path = self.input_path(day);
+        match crypt::read_or_decrypt(&path, || self.key()) {
+            Ok(input) => {
+                return String::from_utf8(input)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e).into())
+            }
+            Err(CryptError::Io(e)) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        let input = self
+            .request("GET", &format!("/{}/day/{day}/input", self.year))?
+            .call()?
+            .into_string()?;
+        fs::create_dir_all(&self.inputs_dir)?;
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+
+    /// Build an authenticated request to `path` on the website.
+    pub(crate) fn request(&self, method: &str, path: &str) -> Result<ureq::Request, ClientError> {
+        let session = self.session.as_ref().ok_or(ClientError::MissingSession)?;
+        Ok(self
+            .agent
+            .request(method, &format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={session}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Client, ClientError, USER_AGENT};
+    use crate::crypt::{CryptError, Key};
+    use crate::test_server::{temp_dir, TestServer};
+
+    /// Verify that inputs are downloaded with the session cookie and then cached.
+    #[test]
+    fn fetch_and_cache() {
+        let server = TestServer::start(|request| match request.path.as_str() {
+            "/2024/day/3/input" => (200, "mul(2,4)\n".to_owned()),
+            _ => (404, "not found".to_owned()),
+        });
+        let inputs_dir = temp_dir("fetch_and_cache");
+        let client = Client::from_env()
+            .with_base_url(&server.url)
+            .with_session("secret")
+            .with_inputs_dir(&inputs_dir);
+        assert_eq!(client.fetch_input(3).unwrap(), "mul(2,4)\n");
+        assert_eq!(client.fetch_input(3).unwrap(), "mul(2,4)\n");
+        let requests = server.requests();
+        // The second call is served from the cache.
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+        assert_eq!(requests[0].header("user-agent"), Some(USER_AGENT));
+        assert_eq!(
+            std::fs::read_to_string(inputs_dir.join("day3.txt")).unwrap(),
+            "mul(2,4)\n"
+        );
+    }
+
+    /// Verify that failed downloads are reported and not cached.
+    #[test]
+    fn fetch_error() {
+        let server = TestServer::start(|_| (400, "Please log in".to_owned()));
+        let inputs_dir = temp_dir("fetch_error");
+        let client = Client::from_env()
+            .with_base_url(&server.url)
+            .with_session("expired")
+            .with_inputs_dir(&inputs_dir);
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::Status { status: 400, body }) if body == "Please log in"
+        ));
+        assert!(!inputs_dir.join("day1.txt").exists());
+    }
+
+    /// Verify that encrypted inputs are decrypted instead of being downloaded.
+    #[test]
+    fn fetch_encrypted() {
+        let server = TestServer::start(|_| (404, "not found".to_owned()));
+        let inputs_dir = temp_dir("fetch_encrypted");
+        let key = Key::generate();
+        std::fs::write(inputs_dir.join("day2.txt.enc"), key.encrypt(b"1 2 3\n")).unwrap();
+        let client = Client::from_env()
+            .with_base_url(&server.url)
+            .with_session("secret")
+            .with_inputs_dir(&inputs_dir);
+        assert_eq!(
+            client.clone().with_key(key).fetch_input(2).unwrap(),
+            "1 2 3\n"
+        );
+        assert!(matches!(
+            client.with_key(Key::generate()).fetch_input(2),
+            Err(ClientError::Crypt(CryptError::Decryption))
+        ));
+        assert!(server.requests().is_empty());
+    }
+
+    /// Verify that a session cookie is required to download inputs.
+    #[test]
+    fn fetch_missing_session() {
+        let client = Client::from_env().with_inputs_dir(temp_dir("fetch_missing_session"));
+        let client = Client {
+            session: None,
+            ..client
+        };
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::MissingSession)
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+//! Encryption of puzzle inputs, so they can be shared through the repository
+//! without publishing them.
+//!
+//! Encrypted files start with [`MAGIC`], followed by a random nonce and the
+//! XChaCha20-Poly1305 ciphertext of the file.
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Environment variable that holds the key as a hex string.
+pub const KEY_ENV: &str = "AOC_INPUTS_KEY";
+/// Environment variable that holds the path to a file containing the key as a hex string.
+pub const KEYFILE_ENV: &str = "AOC_INPUTS_KEYFILE";
+/// Header of encrypted files.
+pub const MAGIC: &[u8] = b"aoc-enc1";
+/// Extension appended to the names of encrypted files.
+pub const EXTENSION: &str = "enc";
+
+/// Length of the nonce stored after [`MAGIC`].
+const NONCE_LEN: usize = 24;
+
+/// Default location of the key file, in the workspace root.
+pub fn default_keyfile() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../.inputs-key")
+}
+
+/// Path of the encrypted version of the file at `path`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Encrypt the file at `path` into the file at [`encrypted_path`].
+///
+/// Returns `false` without writing anything if the existing encrypted file already decrypts
+/// to the same contents, so re-encrypting unchanged files doesn't produce a diff.
+pub fn encrypt_file(key: &Key, path: &Path) -> Result<bool, CryptError> {
+    let plaintext = fs::read(path)?;
+    let encrypted_path = encrypted_path(path);
+    match fs::read(&encrypted_path) {
+        Ok(existing)
+            if key
+                .decrypt(&existing)
+                .is_ok_and(|existing| existing == plaintext) =>
+        {
+            return Ok(false)
+        }
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    fs::write(encrypted_path, key.encrypt(&plaintext))?;
+    Ok(true)
+}
+
+/// Read the file at `path`, or decrypt the file at [`encrypted_path`] if it doesn't exist.
+///
+/// The key is only requested if the file has to be decrypted.
+pub fn read_or_decrypt(
+    path: &Path,
+    key: impl FnOnce() -> Result<Key, CryptError>,
+) -> Result<Vec<u8>, CryptError> {
+    let not_found = match fs::read(path) {
+        Ok(contents) => return Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => e,
+        Err(e) => return Err(e.into()),
+    };
+    let encrypted = match fs::read(encrypted_path(path)) {
+        Ok(encrypted) => encrypted,
+        // Report the missing plaintext file rather than the encrypted one.
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(not_found.into()),
+        Err(e) => return Err(e.into()),
+    };
+    key()?.decrypt(&encrypted)
+}
+
+/// Error returned when encryption or decryption fails.
+#[derive(Debug, Error)]
+pub enum CryptError {
+    #[error(
+        "key not found (set `{KEY_ENV}`, set `{KEYFILE_ENV}` or create `{}`)",
+        default_keyfile().display()
+    )]
+    MissingKey,
+    #[error("key must be 64 hex digits")]
+    InvalidKey,
+    #[error("not an encrypted file")]
+    InvalidFormat,
+    #[error("decryption failed (wrong key or corrupted file)")]
+    Decryption,
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Key used to encrypt and decrypt inputs.
+#[derive(Clone)]
+pub struct Key(chacha20poly1305::Key);
+
+impl fmt::Debug for Key {
+    /// Don't leak the key into logs.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+impl Key {
+    /// Generate a new random key.
+    pub fn generate() -> Self {
+        Key(XChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    /// Load the key from the [`KEY_ENV`] environment variable, or the key file at
+    /// [`KEYFILE_ENV`] or [`default_keyfile`], in that order.
+    pub fn from_env() -> Result<Self, CryptError> {
+        if let Ok(hex) = env::var(KEY_ENV) {
+            return hex.parse();
+        }
+        let keyfile = env::var_os(KEYFILE_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(default_keyfile);
+        match fs::read_to_string(keyfile) {
+            Ok(hex) => hex.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(CryptError::MissingKey),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Encrypt `plaintext` with a random nonce.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = XChaCha20Poly1305::new(&self.0)
+            .encrypt(&nonce, plaintext)
+            .expect("plaintext fits in memory");
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    /// Decrypt data produced by [`Key::encrypt`].
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptError> {
+        let data = data.strip_prefix(MAGIC).ok_or(CryptError::InvalidFormat)?;
+        if data.len() < NONCE_LEN {
+            return Err(CryptError::InvalidFormat);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        XChaCha20Poly1305::new(&self.0)
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptError::Decryption)
+    }
+}
+
+impl std::str::FromStr for Key {
+    type Err = CryptError;
+
+    /// Parse a key from 64 hex digits. Surrounding whitespace is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().as_bytes();
+        if s.len() != 64 {
+            return Err(CryptError::InvalidKey);
+        }
+        let mut key = chacha20poly1305::Key::default();
+        for (byte, digits) in key.iter_mut().zip(s.chunks(2)) {
+            let digits = std::str::from_utf8(digits).map_err(|_| CryptError::InvalidKey)?;
+            *byte = u8::from_str_radix(digits, 16).map_err(|_| CryptError::InvalidKey)?;
+        }
+        Ok(Key(key))
+    }
+}
+
+impl fmt::Display for Key {
+    /// Format the key as 64 hex digits.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encrypt_file, encrypted_path, read_or_decrypt, CryptError, Key};
+    use crate::test_server::temp_dir;
+
+    /// Verify that encrypted data decrypts to the original with the same key only.
+    #[test]
+    fn round_trip() {
+        let key = Key::generate();
+        let encrypted = key.encrypt(b"3   4\n4   3\n");
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"3   4\n4   3\n");
+        assert!(matches!(
+            Key::generate().decrypt(&encrypted),
+            Err(CryptError::Decryption)
+        ));
+        let mut tampered = encrypted.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            key.decrypt(&tampered),
+            Err(CryptError::Decryption)
+        ));
+        assert!(matches!(
+            key.decrypt(b"3   4\n"),
+            Err(CryptError::InvalidFormat)
+        ));
+    }
+
+    /// Verify that keys survive a round trip through their hex representation.
+    #[test]
+    fn key_hex() {
+        let key = Key::generate();
+        let parsed: Key = format!("{key}\n").parse().unwrap();
+        assert_eq!(parsed.to_string(), key.to_string());
+        assert!(matches!("abc".parse::<Key>(), Err(CryptError::InvalidKey)));
+        assert!(matches!(
+            "zz".repeat(32).parse::<Key>(),
+            Err(CryptError::InvalidKey)
+        ));
+    }
+
+    /// Verify that unchanged files are not re-encrypted and missing files are decrypted.
+    #[test]
+    fn encrypt_file_unchanged() {
+        let key = Key::generate();
+        let path = temp_dir("encrypt_file_unchanged").join("day1.txt");
+        std::fs::write(&path, "3   4\n").unwrap();
+        assert!(encrypt_file(&key, &path).unwrap());
+        let encrypted = std::fs::read(encrypted_path(&path)).unwrap();
+        assert!(!encrypt_file(&key, &path).unwrap());
+        assert_eq!(std::fs::read(encrypted_path(&path)).unwrap(), encrypted);
+        std::fs::write(&path, "4   3\n").unwrap();
+        assert!(encrypt_file(&key, &path).unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+        let decrypted = read_or_decrypt(&path, || Ok(key.clone())).unwrap();
+        assert_eq!(decrypted, b"4   3\n");
+        std::fs::remove_file(encrypted_path(&path)).unwrap();
+        assert!(matches!(
+            read_or_decrypt(&path, || Ok(key.clone())),
+            Err(CryptError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound
+        ));
+    }
+}
@@ -1,5 +1,17 @@
+//! Utilities for reading puzzle inputs.
+
 use std::{fs, io, path::PathBuf};
 
+mod client;
+pub mod crypt;
+mod submit;
+#[cfg(test)]
+mod test_server;
+
+pub use client::{inputs_dir, Client, ClientError, BASE_URL, INPUTS_DIR_ENV, SESSION_ENV, YEAR};
+pub use crypt::{CryptError, Key};
+pub use submit::{Attempt, History, Outcome, SubmitError};
+
 fn stdin_path() -> PathBuf {
     println!();
     println!("enter path to input: ");
@@ -8,16 +20,36 @@ fn stdin_path() -> PathBuf {
     PathBuf::from(input.trim())
 }
 
-pub fn stdin_file() -> String {
+/// Prompt for a path on standard input until the file at that path is read successfully
+/// with the provided `read` function.
+fn stdin_read_with<T>(read: impl Fn(PathBuf) -> io::Result<T>) -> T {
     loop {
         let path = stdin_path();
         println!(
             "reading: {}",
             path.canonicalize().as_ref().unwrap_or(&path).display()
         );
-        match fs::read_to_string(path) {
+        match read(path) {
             Ok(s) => return s,
             Err(e) => eprintln!("file read io error: {e}"),
         }
     }
 }
+
+/// Read the input file at a path provided on standard input as a string.
+/// The file must contain valid UTF-8.
+pub fn stdin_file() -> String {
+    stdin_read_with(fs::read_to_string)
+}
+
+/// Read the input file at a path provided on standard input as raw bytes.
+/// Unlike [`stdin_file`], the file may contain invalid UTF-8.
+pub fn stdin_file_bytes() -> Vec<u8> {
+    stdin_read_with(fs::read)
+}
+
+/// Open the input file at a path provided on standard input for buffered reading.
+/// The file is read in chunks of at most `capacity` bytes.
+pub fn stdin_file_reader(capacity: usize) -> io::BufReader<fs::File> {
+    stdin_read_with(|path| fs::File::open(path).map(|f| io::BufReader::with_capacity(capacity, f)))
+}
@@ -0,0 +1,517 @@
+//! Answer submission with a local history of attempts.
+//!
+//! Every submitted answer is recorded in a history file in the inputs directory.
+//! The history is used to refuse answers that are known to be wrong without sending them.
+
+use crate::{Client, ClientError};
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+/// Response of the website to a submitted answer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    Correct,
+    /// The answer is wrong and greater than the correct answer.
+    TooHigh,
+    /// The answer is wrong and less than the correct answer.
+    TooLow,
+    /// The answer is wrong. No hint was given.
+    Incorrect,
+    /// An answer was submitted too recently. The answer was not checked.
+    Wait(Duration),
+    /// The part is not available, e.g. because it was already completed.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Parse the response page of the website.
+    /// Returns `None` if the response is not recognized.
+    fn parse_response(body: &str) -> Option<Self> {
+        let text = body.to_lowercase();
+        if text.contains("that's the right answer") {
+            Some(Outcome::Correct)
+        } else if text.contains("that's not the right answer") {
+            if text.contains("your answer is too high") {
+                Some(Outcome::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Some(Outcome::TooLow)
+            } else {
+                Some(Outcome::Incorrect)
+            }
+        } else if text.contains("you gave an answer too recently") {
+            // e.g. "you have 1m 23s left to wait"
+            let (_, wait) = text.rsplit_once("you have ")?;
+            let (wait, _) = wait.split_once(" left to wait")?;
+            parse_duration(wait).map(Outcome::Wait)
+        } else if text.contains("you don't seem to be solving the right level") {
+            Some(Outcome::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a duration in the format used by the website, e.g. `1m 23s`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    s.split_whitespace().try_fold(Duration::ZERO, |acc, part| {
+        let seconds = match part.split_at_checked(part.len().checked_sub(1)?)? {
+            (n, "h") => n.parse::<u64>().ok()? * 3600,
+            (n, "m") => n.parse::<u64>().ok()? * 60,
+            (n, "s") => n.parse::<u64>().ok()?,
+            _ => return None,
+        };
+        Some(acc + Duration::from_secs(seconds))
+    })
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::Wait(wait) => write!(f, "wait-{}s", wait.as_secs()),
+            Outcome::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "incorrect" => Ok(Outcome::Incorrect),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            _ => s
+                .strip_prefix("wait-")
+                .and_then(|s| s.strip_suffix('s'))
+                .and_then(|s| s.parse().ok())
+                .map(|secs| Outcome::Wait(Duration::from_secs(secs)))
+                .ok_or_else(|| format!("unknown outcome `{s}`")),
+        }
+    }
+}
+
+/// Error returned when an answer is not submitted or the submission fails.
+#[derive(Debug, Error)]
+pub enum SubmitError {
+    #[error("part {part} must be either 1 or 2")]
+    InvalidPart { part: u8 },
+    #[error("answer {answer:?} must not contain tabs or line breaks")]
+    InvalidAnswer { answer: String },
+    #[error("day {day} part {part} was already solved with answer `{answer}`")]
+    AlreadySolved { day: u8, part: u8, answer: String },
+    #[error("answer `{answer}` was already submitted and is {outcome}")]
+    KnownWrong { answer: String, outcome: Outcome },
+    #[error("answer `{answer}` is out of bounds: `{bound}` was {outcome}")]
+    OutOfBounds {
+        answer: String,
+        bound: String,
+        outcome: Outcome,
+    },
+    #[error("an answer was submitted too recently, wait {}s", .0.as_secs())]
+    TooSoon(Duration),
+    #[error("unrecognized response: {0}")]
+    UnrecognizedResponse(String),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+}
+
+impl From<io::Error> for SubmitError {
+    fn from(error: io::Error) -> Self {
+        SubmitError::Client(error.into())
+    }
+}
+
+/// A submitted answer recorded in the [`History`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+impl Attempt {
+    /// Parse a line of the history file.
+    fn parse_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let attempt = Attempt {
+            time: fields.next()?.parse().ok()?,
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            answer: fields.next()?.to_owned(),
+            outcome: fields.next()?.parse().ok()?,
+        };
+        fields.next().is_none().then_some(attempt)
+    }
+}
+
+impl Display for Attempt {
+    /// Format as a line of the history file.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Attempt {
+            time,
+            day,
+            part,
+            answer,
+            outcome,
+        } = self;
+        write!(f, "{time}\t{day}\t{part}\t{answer}\t{outcome}")
+    }
+}
+
+/// Local history of submitted answers.
+///
+/// Stored as a tab-separated file with one [`Attempt`] per line.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Load the history file at `path`. A missing file is treated as an empty history.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let attempts = match fs::read_to_string(&path) {
+            Ok(s) => s
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| {
+                    Attempt::parse_line(line).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("invalid history entry `{line}`"),
+                        )
+                    })
+                })
+                .collect::<io::Result<_>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(History { path, attempts })
+    }
+
+    /// Path of the history file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All recorded attempts, oldest first.
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Append an attempt to the history file.
+    pub fn record(&mut self, attempt: Attempt) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{attempt}")?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Check whether `answer` for `day` and `part` may be submitted at time `now`
+    /// (in seconds since the Unix epoch).
+    ///
+    /// Refuses answers for parts that were already solved, answers that were already
+    /// found to be wrong and numeric answers outside of the bounds established by
+    /// previous "too high" or "too low" responses.
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<(), SubmitError> {
+        if let Some(wait) = self.cooldown(now) {
+            return Err(SubmitError::TooSoon(wait));
+        }
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part);
+        for attempt in attempts {
+            let out_of_bounds = match (answer.parse::<i128>(), attempt.answer.parse::<i128>()) {
+                (Ok(answer), Ok(bound)) => match attempt.outcome {
+                    Outcome::TooHigh => answer >= bound,
+                    Outcome::TooLow => answer <= bound,
+                    _ => false,
+                },
+                _ => false,
+            };
+            match attempt.outcome {
+                Outcome::Correct => {
+                    return Err(SubmitError::AlreadySolved {
+                        day,
+                        part,
+                        answer: attempt.answer.clone(),
+                    })
+                }
+                Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+                    if attempt.answer == answer =>
+                {
+                    return Err(SubmitError::KnownWrong {
+                        answer: answer.to_owned(),
+                        outcome: attempt.outcome,
+                    })
+                }
+                _ if out_of_bounds => {
+                    return Err(SubmitError::OutOfBounds {
+                        answer: answer.to_owned(),
+                        bound: attempt.answer.clone(),
+                        outcome: attempt.outcome,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Remaining time to wait at time `now` if the last attempt was rejected
+    /// for being too recent.
+    fn cooldown(&self, now: u64) -> Option<Duration> {
+        match self.attempts.last()? {
+            Attempt {
+                time,
+                outcome: Outcome::Wait(wait),
+                ..
+            } => {
+                let until = time + wait.as_secs();
+                (now < until).then(|| Duration::from_secs(until - now))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Current time in seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time is after the unix epoch")
+        .as_secs()
+}
+
+impl Client {
+    /// Path of the file the submission [`History`] is stored in.
+    pub fn history_path(&self) -> PathBuf {
+        self.inputs_dir.join("submissions.tsv")
+    }
+
+    /// Submit `answer` for `part` of `day` and record the outcome in the [`History`].
+    ///
+    /// The answer is not sent if the history shows it cannot be correct, or if it contains
+    /// tabs or line breaks, which can't be recorded.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Outcome, SubmitError> {
+        if !(1..=2).contains(&part) {
+            return Err(SubmitError::InvalidPart { part });
+        }
+        let answer = answer.trim();
+        // Attempts are recorded as tab-separated lines.
+        if answer.contains(['\t', '\n', '\r']) {
+            return Err(SubmitError::InvalidAnswer {
+                answer: answer.to_owned(),
+            });
+        }
+        let mut history = History::load(self.history_path())?;
+        history.check(day, part, answer, now())?;
+        let body = self
+            .request("POST", &format!("/{}/day/{day}/answer", self.year))?
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(ClientError::from)?
+            .into_string()?;
+        let outcome =
+            Outcome::parse_response(&body).ok_or(SubmitError::UnrecognizedResponse(body))?;
+        history.record(Attempt {
+            time: now(),
+            day,
+            part,
+            answer: answer.to_owned(),
+            outcome,
+        })?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Attempt, History, Outcome, SubmitError};
+    use crate::{
+        test_server::{temp_dir, TestServer},
+        Client,
+    };
+    use std::time::Duration;
+
+    /// Response pages of the website, reduced to the relevant text.
+    const RIGHT: &str =
+        "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p></article>";
+    const TOO_LOW: &str =
+        "<article><p>That's not the right answer; your answer is too low.</p></article>";
+    const WAIT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.</p></article>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>";
+
+    /// Verify that responses are recognized.
+    #[test]
+    fn parse_response() {
+        assert_eq!(Outcome::parse_response(RIGHT), Some(Outcome::Correct));
+        assert_eq!(Outcome::parse_response(TOO_HIGH), Some(Outcome::TooHigh));
+        assert_eq!(Outcome::parse_response(TOO_LOW), Some(Outcome::TooLow));
+        assert_eq!(
+            Outcome::parse_response(WAIT),
+            Some(Outcome::Wait(Duration::from_secs(83)))
+        );
+        assert_eq!(
+            Outcome::parse_response(WRONG_LEVEL),
+            Some(Outcome::WrongLevel)
+        );
+        assert_eq!(Outcome::parse_response("<html></html>"), None);
+    }
+
+    /// Verify that history entries survive a round trip through the history file.
+    #[test]
+    fn history_round_trip() {
+        let path = temp_dir("history_round_trip").join("submissions.tsv");
+        let mut history = History::load(&path).unwrap();
+        let attempts = [
+            (Outcome::TooLow, "10"),
+            (Outcome::Wait(Duration::from_secs(60)), "12"),
+            (Outcome::Correct, "11"),
+        ]
+        .map(|(outcome, answer)| Attempt {
+            time: 1,
+            day: 2,
+            part: 1,
+            answer: answer.to_owned(),
+            outcome,
+        });
+        attempts
+            .iter()
+            .for_each(|attempt| history.record(attempt.clone()).unwrap());
+        assert_eq!(History::load(&path).unwrap().attempts(), attempts);
+    }
+
+    /// Verify that answers known to be wrong are refused.
+    #[test]
+    fn history_check() {
+        let mut history = History::load(temp_dir("history_check").join("h.tsv")).unwrap();
+        let mut record = |part, answer: &str, outcome| {
+            let attempt = Attempt {
+                time: 100,
+                day: 1,
+                part,
+                answer: answer.to_owned(),
+                outcome,
+            };
+            history.record(attempt).unwrap();
+        };
+        record(1, "50", Outcome::TooHigh);
+        record(1, "10", Outcome::TooLow);
+        record(1, "20", Outcome::Incorrect);
+        record(2, "7", Outcome::Correct);
+        let check = |part, answer| history.check(1, part, answer, 1000);
+        assert!(check(1, "30").is_ok());
+        assert!(matches!(
+            check(1, "20"),
+            Err(SubmitError::KnownWrong { .. })
+        ));
+        assert!(matches!(
+            check(1, "50"),
+            Err(SubmitError::KnownWrong { .. })
+        ));
+        assert!(matches!(
+            check(1, "51"),
+            Err(SubmitError::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            check(1, "9"),
+            Err(SubmitError::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            check(2, "8"),
+            Err(SubmitError::AlreadySolved { .. })
+        ));
+        // Other days are unaffected.
+        assert!(history.check(2, 1, "20", 1000).is_ok());
+    }
+
+    /// Verify that the guard refuses to submit until the wait time has passed.
+    #[test]
+    fn history_cooldown() {
+        let mut history = History::load(temp_dir("history_cooldown").join("h.tsv")).unwrap();
+        let attempt = Attempt {
+            time: 100,
+            day: 1,
+            part: 1,
+            answer: "5".to_owned(),
+            outcome: Outcome::Wait(Duration::from_secs(60)),
+        };
+        history.record(attempt).unwrap();
+        assert!(matches!(
+            history.check(1, 1, "5", 130),
+            Err(SubmitError::TooSoon(wait)) if wait == Duration::from_secs(30)
+        ));
+        assert!(history.check(1, 1, "5", 160).is_ok());
+    }
+
+    /// Verify submission against a stand-in server, including the history guard.
+    #[test]
+    fn submit() {
+        let server =
+            TestServer::start(
+                |request| match (request.path.as_str(), request.body.as_str()) {
+                    ("/2024/day/4/answer", "level=1&answer=100") => (200, TOO_HIGH.to_owned()),
+                    ("/2024/day/4/answer", "level=1&answer=18") => (200, RIGHT.to_owned()),
+                    _ => (404, "not found".to_owned()),
+                },
+            );
+        let inputs_dir = temp_dir("submit");
+        let client = Client::from_env()
+            .with_base_url(&server.url)
+            .with_session("secret")
+            .with_inputs_dir(&inputs_dir);
+        assert_eq!(client.submit(4, 1, "100").unwrap(), Outcome::TooHigh);
+        assert!(matches!(
+            client.submit(4, 1, "150"),
+            Err(SubmitError::OutOfBounds { .. })
+        ));
+        assert_eq!(client.submit(4, 1, "18").unwrap(), Outcome::Correct);
+        assert!(matches!(
+            client.submit(4, 1, "18"),
+            Err(SubmitError::AlreadySolved { .. })
+        ));
+        assert!(matches!(
+            client.submit(4, 3, "18"),
+            Err(SubmitError::InvalidPart { part: 3 })
+        ));
+        for answer in ["1\t2", "1\n2", "1\r2"] {
+            assert!(matches!(
+                client.submit(4, 2, answer),
+                Err(SubmitError::InvalidAnswer { .. })
+            ));
+        }
+        // Refused answers are never sent.
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+        let history = History::load(client.history_path()).unwrap();
+        assert_eq!(history.attempts().len(), 2);
+    }
+}
@@ -0,0 +1,108 @@
+//! Local HTTP server that stands in for the Advent of Code website in tests.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::mpsc,
+    thread,
+};
+
+/// Request received by a [`TestServer`].
+#[derive(Debug, Clone)]
+pub(crate) struct Request {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    /// Header names are lowercase.
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: String,
+}
+
+impl Request {
+    /// Value of the header with the provided lowercase name.
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// HTTP server running on a background thread.
+/// Responds to every request with the status and body returned by a handler.
+pub(crate) struct TestServer {
+    /// Base URL of the server, e.g. `http://127.0.0.1:1234`.
+    pub(crate) url: String,
+    requests: mpsc::Receiver<Request>,
+}
+
+impl TestServer {
+    /// Start a server on a free local port.
+    pub(crate) fn start(handler: impl Fn(&Request) -> (u16, String) + Send + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let url = format!("http://{}", listener.local_addr().expect("local address"));
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.expect("test server connection");
+                let request = read_request(&mut BufReader::new(&stream));
+                let (status, body) = handler(&request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Test\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .expect("test server write");
+                // Stop serving once the server is dropped.
+                if sender.send(request).is_err() {
+                    return;
+                }
+            }
+        });
+        TestServer { url, requests }
+    }
+
+    /// Requests received since the last call.
+    pub(crate) fn requests(&self) -> Vec<Request> {
+        self.requests.try_iter().collect()
+    }
+}
+
+/// Read a single HTTP/1.1 request.
+fn read_request(reader: &mut impl BufRead) -> Request {
+    let mut lines = reader
+        .by_ref()
+        .lines()
+        .map(|line| line.expect("request line"));
+    let request_line = lines.next().expect("request line");
+    let mut parts = request_line.split(' ');
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+    let headers: Vec<_> = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_lowercase(), value.trim().to_owned()))
+        })
+        .collect();
+    let length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .map_or(0, |(_, value)| value.parse().expect("content length"));
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).expect("request body");
+    Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8(body).expect("utf-8 body"),
+    }
+}
+
+/// Create an empty directory for a test in the system temporary directory.
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-input-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create temporary directory");
+    dir
+}
@@ -0,0 +1,164 @@
+//! Integer types selected at runtime, for solutions that are generic over their integer type.
+//!
+//! [`Bounds::of`] scans an input for its integers, so a solution can pick the
+//! [narrowest](IntType::narrowest) type that holds them and its answers, and dispatch to
+//! the matching instantiation.
+
+use std::{fmt, num::ParseIntError, ops::RangeInclusive, str::FromStr};
+
+/// Integer type a generic solution can be instantiated with.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IntType {
+    U16,
+    U32,
+    U64,
+    /// Signed integers. Required by inputs with negative integers.
+    I64,
+}
+
+impl IntType {
+    /// Every type, from the narrowest to the widest.
+    pub const ALL: [IntType; 4] = [IntType::U16, IntType::U32, IntType::U64, IntType::I64];
+
+    /// Values the type can hold.
+    pub fn range(self) -> RangeInclusive<i128> {
+        match self {
+            IntType::U16 => 0..=u16::MAX.into(),
+            IntType::U32 => 0..=u32::MAX.into(),
+            IntType::U64 => 0..=u64::MAX.into(),
+            IntType::I64 => i64::MIN.into()..=i64::MAX.into(),
+        }
+    }
+
+    /// Whether the type can hold every value in `min..=max`.
+    pub fn holds(self, min: i128, max: i128) -> bool {
+        let range = self.range();
+        range.contains(&min) && range.contains(&max)
+    }
+
+    /// Narrowest type that can hold every value in `min..=max`, if any.
+    pub fn narrowest(min: i128, max: i128) -> Option<Self> {
+        Self::ALL.into_iter().find(|int| int.holds(min, max))
+    }
+}
+
+impl FromStr for IntType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "u16" => Ok(IntType::U16),
+            "u32" => Ok(IntType::U32),
+            "u64" => Ok(IntType::U64),
+            "i64" => Ok(IntType::I64),
+            _ => Err(format!(
+                "unknown integer type `{s}` (expected `u16`, `u32`, `u64` or `i64`)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::I64 => "i64",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Number and extremes of the whitespace-separated integers in an input.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Bounds {
+    pub count: usize,
+    /// Smallest integer, or 0 if there are none.
+    pub min: i128,
+    /// Largest integer, or 0 if there are none.
+    pub max: i128,
+}
+
+impl Bounds {
+    /// Scan `input` for its integers.
+    /// Fails if any whitespace-separated token is not an integer that fits in an `i128`.
+    pub fn of(input: &str) -> Result<Self, ParseIntError> {
+        let mut integers = input.split_ascii_whitespace().map(str::parse::<i128>);
+        let Some(first) = integers.next().transpose()? else {
+            return Ok(Self::default());
+        };
+        integers.try_fold(
+            Bounds {
+                count: 1,
+                min: first,
+                max: first,
+            },
+            |bounds, integer| {
+                let integer = integer?;
+                Ok(Bounds {
+                    count: bounds.count + 1,
+                    min: bounds.min.min(integer),
+                    max: bounds.max.max(integer),
+                })
+            },
+        )
+    }
+}
+
+/// Select the integer type to solve `input` with: `requested` if any, otherwise the type
+/// `auto` picks for the bounds of `input`.
+///
+/// Fails if `input` contains tokens that are not integers, or if no type is picked,
+/// or the requested type cannot hold the integers in `input`.
+pub fn select(
+    input: &str,
+    requested: Option<IntType>,
+    auto: impl FnOnce(&Bounds) -> Option<IntType>,
+) -> Result<IntType, String> {
+    let bounds = Bounds::of(input).map_err(|e| format!("input must only contain integers: {e}"))?;
+    match requested {
+        Some(int) if int.holds(bounds.min, bounds.max) => Ok(int),
+        Some(int) => Err(format!(
+            "`{int}` cannot hold integers between {} and {}",
+            bounds.min, bounds.max
+        )),
+        None => auto(&bounds).ok_or_else(|| {
+            format!(
+                "no integer type can solve inputs with integers between {} and {}",
+                bounds.min, bounds.max
+            )
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select, Bounds, IntType};
+
+    /// Verify that the narrowest type holding the bounds of an input is selected.
+    #[test]
+    fn narrowest() {
+        let narrowest = |input| {
+            let bounds = Bounds::of(input).unwrap();
+            IntType::narrowest(bounds.min, bounds.max)
+        };
+        assert_eq!(narrowest("1 2\n3 65535"), Some(IntType::U16));
+        assert_eq!(narrowest("1 65536"), Some(IntType::U32));
+        assert_eq!(narrowest("18446744073709551615 0"), Some(IntType::U64));
+        assert_eq!(narrowest("-1 5"), Some(IntType::I64));
+        assert_eq!(narrowest("-1 18446744073709551615"), None);
+        assert_eq!(Bounds::of("").unwrap(), Bounds::default());
+        assert!(Bounds::of("1 x").is_err());
+    }
+
+    /// Verify that requested types must hold the input.
+    #[test]
+    fn requested() {
+        let auto = |bounds: &Bounds| IntType::narrowest(bounds.min, bounds.max);
+        assert_eq!(select("1 2", None, auto), Ok(IntType::U16));
+        assert_eq!(select("1 2", Some(IntType::I64), auto), Ok(IntType::I64));
+        assert!(select("-1 2", Some(IntType::U64), auto).is_err());
+        assert!(select("1 x", None, auto).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+//! Common interface of puzzle solutions, [`Solution`].
+
+pub mod int;
+pub mod samples;
+
+/// Solution to both parts of a single day's puzzle.
+///
+/// Allows running solutions without knowing anything about their implementation,
+/// e.g. to check answers of every day at once.
+pub trait Solution {
+    /// Day of the puzzle, starting at 1.
+    const DAY: u8;
+
+    /// Puzzle input after parsing.
+    type Input;
+
+    /// Parse the raw puzzle input.
+    fn parse(input: &[u8]) -> Self::Input;
+
+    /// Solve part 1 of the puzzle. Returns the answer as it would be submitted.
+    fn part1(input: &Self::Input) -> String;
+
+    /// Solve part 2 of the puzzle. Returns the answer as it would be submitted.
+    fn part2(input: &Self::Input) -> String;
+}
@@ -0,0 +1,192 @@
+//! Sample inputs with expected answers, listed in a manifest next to each day's crate.
+//!
+//! The manifest is a `samples.toml` file in the root of the day's crate, with a table for
+//! each sample file and an optional answer for each part:
+//!
+//! ```toml
+//! [[sample]]
+//! file = "sample_input.txt"
+//! part1 = 11
+//! part2 = "31"
+//! ```
+//!
+//! [`sample_tests!`](crate::sample_tests) generates a test that checks every sample, so
+//! adding a sample only takes a new file and a manifest entry.
+
+use crate::Solution;
+use serde::Deserialize;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// File name of the manifest in a day's crate.
+pub const MANIFEST: &str = "samples.toml";
+
+/// Error returned when the manifest or a sample cannot be loaded.
+#[derive(Debug, Error)]
+pub enum SamplesError {
+    #[error("failed to read `{}`: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("failed to parse `{}`: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+/// Answer stored either as a TOML integer or string.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Integer(i64),
+    String(String),
+}
+
+impl fmt::Display for Answer {
+    /// Format the answer as solutions return it, without surrounding whitespace.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(answer) => answer.fmt(f),
+            Answer::String(answer) => answer.trim().fmt(f),
+        }
+    }
+}
+
+/// Sample input file and its expected answers.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Sample {
+    /// Path of the sample input, relative to the manifest.
+    pub file: PathBuf,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Every sample of a single day.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(default, rename = "sample")]
+    pub samples: Vec<Sample>,
+}
+
+impl Manifest {
+    /// Load the manifest in `dir`.
+    pub fn load(dir: &Path) -> Result<Self, SamplesError> {
+        let path = dir.join(MANIFEST);
+        let s = read(&path)?;
+        toml::from_str(&s).map_err(|source| SamplesError::Parse { path, source })
+    }
+}
+
+/// Read the file at `path` to a string.
+fn read(path: &Path) -> Result<String, SamplesError> {
+    fs::read_to_string(path).map_err(|source| SamplesError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Solve every sample listed in the manifest in `dir` with `S`.
+///
+/// # Panics
+/// Panics if the manifest or a sample cannot be loaded, the manifest lists no samples,
+/// or any answer is wrong. Every sample is checked before panicking.
+pub fn check<S: Solution>(dir: impl AsRef<Path>) {
+    let dir = dir.as_ref();
+    let manifest = Manifest::load(dir).unwrap_or_else(|e| panic!("{e}"));
+    assert!(
+        !manifest.samples.is_empty(),
+        "`{}` lists no samples",
+        dir.join(MANIFEST).display()
+    );
+    let mut failures = vec![];
+    for sample in &manifest.samples {
+        let input = read(&dir.join(&sample.file)).unwrap_or_else(|e| panic!("{e}"));
+        let input = S::parse(input.as_bytes());
+        let parts = [
+            ("part 1", &sample.part1, S::part1 as fn(&_) -> _),
+            ("part 2", &sample.part2, S::part2),
+        ];
+        for (name, expected, part) in parts {
+            let Some(expected) = expected else { continue };
+            let answer = part(&input);
+            if answer != expected.to_string() {
+                failures.push(format!(
+                    "day {} {name} of `{}`: expected {expected}, got {answer}",
+                    S::DAY,
+                    sample.file.display()
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Generate a test called `samples` that [checks](check) every sample of a solution,
+/// listed in the manifest of the crate the macro is used in.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     solution::sample_tests!(crate::Day1);
+/// }
+/// ```
+#[macro_export]
+macro_rules! sample_tests {
+    ($solution:ty) => {
+        /// Test both parts on every sample listed in the manifest.
+        #[test]
+        fn samples() {
+            $crate::samples::check::<$solution>(env!("CARGO_MANIFEST_DIR"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answer, Manifest, Sample};
+
+    /// Verify that samples are parsed with integer, string and missing answers.
+    #[test]
+    fn parse() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [[sample]]
+            file = "a.txt"
+            part1 = 11
+            part2 = "abc"
+
+            [[sample]]
+            file = "b.txt"
+            part2 = 48
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            manifest.samples,
+            [
+                Sample {
+                    file: "a.txt".into(),
+                    part1: Some(Answer::Integer(11)),
+                    part2: Some(Answer::String("abc".to_owned())),
+                },
+                Sample {
+                    file: "b.txt".into(),
+                    part1: None,
+                    part2: Some(Answer::Integer(48)),
+                },
+            ]
+        );
+        assert!(toml::from_str::<Manifest>("[[sample]]\nfile = \"a.txt\"\npart3 = 1").is_err());
+    }
+
+    /// Verify that answers are formatted without surrounding whitespace.
+    #[test]
+    fn display() {
+        assert_eq!(Answer::Integer(-11).to_string(), "-11");
+        assert_eq!(Answer::String(" abc\n".to_owned()).to_string(), "abc");
+    }
+}